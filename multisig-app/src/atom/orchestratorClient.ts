@@ -48,6 +48,8 @@ export const AccessRuleInfoSchema = Schema.Struct({
 });
 export type AccessRuleInfo = typeof AccessRuleInfoSchema.Type;
 
+export type FeeMode = "fee_payer" | "multisig" | "reimburse_fee_payer";
+
 export const ProposalSchema = Schema.Struct({
   id: Schema.String,
   manifest_text: Schema.String,
@@ -63,6 +65,13 @@ export const ProposalSchema = Schema.Struct({
   submitted_at: Schema.NullOr(Schema.String),
   tx_id: Schema.NullOr(Schema.String),
   invalid_reason: Schema.NullOr(Schema.String),
+  preview_status: Schema.NullOr(Schema.String),
+  preview_error: Schema.NullOr(Schema.String),
+  preview_fee_estimate: Schema.NullOr(Schema.String),
+  fee_mode: Schema.String,
+  reimbursed_fee_payer: Schema.NullOr(Schema.String),
+  fee_reimbursement: Schema.NullOr(Schema.String),
+  externally_submitted_at: Schema.NullOr(Schema.String),
 });
 export type Proposal = typeof ProposalSchema.Type;
 
@@ -78,6 +87,7 @@ export const SignatureSummarySchema = Schema.Struct({
   signer_public_key: Schema.String,
   signer_key_hash: Schema.String,
   created_at: Schema.String,
+  is_valid: Schema.Boolean,
 });
 export type SignatureSummary = typeof SignatureSummarySchema.Type;
 
@@ -86,18 +96,51 @@ export const SignatureStatusSchema = Schema.Struct({
   signatures: Schema.Array(SignatureSummarySchema),
   threshold: Schema.Number,
   collected: Schema.Number,
+  satisfied: Schema.Boolean,
   remaining: Schema.Number,
   signers: Schema.Array(SignerStatusSchema),
 });
 export type SignatureStatusType = typeof SignatureStatusSchema.Type;
 
 export const SubmitProposalResponseSchema = Schema.Struct({
+  attempt_id: Schema.String,
   status: Schema.String,
-  tx_id: Schema.NullOr(Schema.String),
-  error: Schema.NullOr(Schema.String),
+  tx_id: Schema.String,
 });
 export type SubmitProposalResponse = typeof SubmitProposalResponseSchema.Type;
 
+export const SubmissionAttemptSchema = Schema.Struct({
+  id: Schema.String,
+  fee_payer_account: Schema.NullOr(Schema.String),
+  tx_hash: Schema.NullOr(Schema.String),
+  status: Schema.String,
+  failure_reason: Schema.NullOr(Schema.String),
+  error_message: Schema.NullOr(Schema.String),
+  retryable: Schema.Boolean,
+  fee_estimate: Schema.NullOr(Schema.String),
+  fee_locked: Schema.NullOr(Schema.String),
+  fee_paid: Schema.NullOr(Schema.String),
+  created_at: Schema.String,
+  updated_at: Schema.String,
+});
+export type SubmissionAttempt = typeof SubmissionAttemptSchema.Type;
+
+export const ValidityDecisionSchema = Schema.Struct({
+  id: Schema.String,
+  proposal_id: Schema.String,
+  decision: Schema.Literal(
+    "signature_invalidated",
+    "signature_revalidated",
+    "ready",
+    "back_to_signing",
+    "invalidated"
+  ),
+  signer_key_hash: Schema.NullOr(Schema.String),
+  reason: Schema.String,
+  created_at: Schema.String,
+});
+export type ValidityDecision = typeof ValidityDecisionSchema.Type;
+
 // --- Service definition ---
 
 export class OrchestratorClient extends Context.Tag("OrchestratorClient")<
@@ -111,6 +154,7 @@ export class OrchestratorClient extends Context.Tag("OrchestratorClient")<
       manifest_text: string;
       expiry_epoch: number;
       multisig_account?: string;
+      fee_mode?: FeeMode;
     }) => Effect.Effect<Proposal, Error>;
     readonly listProposals: () => Effect.Effect<ReadonlyArray<Proposal>, Error>;
     readonly getProposal: (id: string) => Effect.Effect<Proposal, Error>;
@@ -124,6 +168,12 @@ export class OrchestratorClient extends Context.Tag("OrchestratorClient")<
     readonly submitProposal: (
       id: string
     ) => Effect.Effect<SubmitProposalResponse, Error>;
+    readonly listSubmissionAttempts: (
+      id: string
+    ) => Effect.Effect<ReadonlyArray<SubmissionAttempt>, Error>;
+    readonly listValidityDecisions: (
+      id: string
+    ) => Effect.Effect<ReadonlyArray<ValidityDecision>, Error>;
   }
 >() {}
 
@@ -172,6 +222,7 @@ const OrchestratorClientLive = Layer.effect(
         manifest_text: string;
         expiry_epoch: number;
         multisig_account?: string;
+        fee_mode?: FeeMode;
       }) =>
         HttpClientRequest.post(`${baseUrl}/proposals`).pipe(
           HttpClientRequest.bodyJson(input),
@@ -257,6 +308,44 @@ const OrchestratorClientLive = Layer.effect(
               )
             )
           ),
+
+      listSubmissionAttempts: (id: string) =>
+        client
+          .execute(
+            HttpClientRequest.get(`${baseUrl}/proposals/${id}/submissions`)
+          )
+          .pipe(
+            Effect.flatMap((res) => res.json),
+            Effect.flatMap(
+              Schema.decodeUnknown(Schema.Array(SubmissionAttemptSchema))
+            ),
+            Effect.scoped,
+            Effect.catchAll((e) =>
+              extractErrorMessage(e).pipe(
+                Effect.flatMap((msg) => Effect.fail(new Error(msg)))
+              )
+            )
+          ),
+
+      listValidityDecisions: (id: string) =>
+        client
+          .execute(
+            HttpClientRequest.get(
+              `${baseUrl}/proposals/${id}/validity-decisions`
+            )
+          )
+          .pipe(
+            Effect.flatMap((res) => res.json),
+            Effect.flatMap(
+              Schema.decodeUnknown(Schema.Array(ValidityDecisionSchema))
+            ),
+            Effect.scoped,
+            Effect.catchAll((e) =>
+              extractErrorMessage(e).pipe(
+                Effect.flatMap((msg) => Effect.fail(new Error(msg)))
+              )
+            )
+          ),
     };
   })
 );
@@ -1,6 +1,7 @@
 import { Effect } from "effect";
 import { makeAtomRuntime } from "./makeRuntimeAtom";
 import {
+  type FeeMode,
   OrchestratorClient,
   OrchestratorClientLayer,
 } from "./orchestratorClient";
@@ -20,6 +21,7 @@ export const createProposalAtom = runtime.fn(
       manifest_text: string;
       expiry_epoch: number;
       multisig_account?: string;
+      fee_mode?: FeeMode;
     },
     get
   ) =>
@@ -54,6 +56,14 @@ export const makeSignatureStatusAtom = (id: string) =>
     })
   );
 
+export const makeSubmissionAttemptsAtom = (id: string) =>
+  runtime.atom(
+    Effect.gen(function* () {
+      const client = yield* OrchestratorClient;
+      return yield* client.listSubmissionAttempts(id);
+    })
+  );
+
 export const makeSignProposalAtom = (
   proposalDetailAtom: ReturnType<typeof makeProposalDetailAtom>,
   signatureStatusAtom: ReturnType<typeof makeSignatureStatusAtom>
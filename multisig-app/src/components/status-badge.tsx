@@ -15,6 +15,9 @@ const STATUS_VARIANTS: Record<string, { className: string }> = {
   failed: { className: "bg-red-500/20 text-red-400 hover:bg-red-500/20" },
   expired: { className: "bg-gray-500/20 text-gray-400 hover:bg-gray-500/20" },
   invalid: { className: "bg-red-500/20 text-red-400 hover:bg-red-500/20" },
+  cancelled: {
+    className: "bg-gray-500/20 text-gray-400 hover:bg-gray-500/20",
+  },
 };
 
 const DEFAULT_VARIANT = {
@@ -3,6 +3,7 @@ import { Result, useAtomValue, useAtomSet } from "@effect-atom/atom-react";
 import {
   makeProposalDetailAtom,
   makeSignatureStatusAtom,
+  makeSubmissionAttemptsAtom,
   makeSubmitProposalAtom,
 } from "@/atom/proposalAtoms";
 import { makeHandleSignAtom } from "@/atom/handleSignAtom";
@@ -162,6 +163,11 @@ function ProposalContent({
         <ValidityWarning proposal={proposal} />
       )}
 
+      {/* Creation-time preview failed but the proposal was kept */}
+      {proposal.preview_status && proposal.preview_status !== "succeeded" && (
+        <PreviewWarning proposal={proposal} />
+      )}
+
       {/* Metadata grid */}
       <div className="grid grid-cols-2 gap-4">
         <MetadataField label="Created" value={created} />
@@ -283,7 +289,9 @@ function SignatureStatusDisplay({
   proposal: Proposal;
   handleSignAtom: ReturnType<typeof makeHandleSignAtom>;
 }) {
-  const pct = Math.min(100, (sigStatus.collected / sigStatus.threshold) * 100);
+  const pct = sigStatus.satisfied
+    ? 100
+    : (sigStatus.collected / (sigStatus.collected + sigStatus.remaining)) * 100;
 
   return (
     <div className="space-y-4">
@@ -294,7 +302,7 @@ function SignatureStatusDisplay({
           <span className="text-muted-foreground">
             of {sigStatus.threshold} signatures collected
           </span>
-          {sigStatus.remaining > 0 && (
+          {!sigStatus.satisfied && (
             <span className="text-xs text-muted-foreground">
               ({sigStatus.remaining} more needed)
             </span>
@@ -436,25 +444,10 @@ function SubmitSection({
         {error && <p className="text-sm text-red-400">{error}</p>}
 
         {result && (
-          <Alert
-            className={
-              result.status === "committed"
-                ? "border-emerald-500/20 bg-emerald-500/10 text-emerald-400"
-                : "border-red-500/20 bg-red-500/10 text-red-400"
-            }
-          >
-            <AlertTitle>
-              {result.status === "committed"
-                ? "Transaction committed!"
-                : `Transaction ${result.status}`}
-            </AlertTitle>
+          <Alert className="border-purple-500/20 bg-purple-500/10 text-purple-400">
+            <AlertTitle>Transaction submitted</AlertTitle>
             <AlertDescription>
-              {result.tx_id && (
-                <p className="font-mono text-xs break-all">
-                  TX: {result.tx_id}
-                </p>
-              )}
-              {result.error && <p className="text-xs">{result.error}</p>}
+              <p className="font-mono text-xs break-all">TX: {result.tx_id}</p>
             </AlertDescription>
           </Alert>
         )}
@@ -465,6 +458,19 @@ function SubmitSection({
 
 function TransactionResult({ proposal }: { proposal: Proposal }) {
   const isCommitted = proposal.status === "committed";
+  const attemptsAtom = useMemo(
+    () => makeSubmissionAttemptsAtom(proposal.id),
+    [proposal.id]
+  );
+  const attemptsResult = useAtomValue(attemptsAtom);
+  const attempts = Result.builder(attemptsResult)
+    .onSuccess((attempts) => attempts)
+    .onInitial(() => [])
+    .onFailure(() => [])
+    .render();
+  const lastFailure =
+    attempts.filter((a) => a.status === "failed").at(-1) ?? null;
+  const feePaid = attempts.filter((a) => a.fee_paid !== null).at(-1)?.fee_paid;
 
   return (
     <Alert
@@ -483,11 +489,24 @@ function TransactionResult({ proposal }: { proposal: Proposal }) {
         >
           {isCommitted ? "Committed on ledger" : "Transaction failed"}
         </p>
+        {!isCommitted && lastFailure?.failure_reason && (
+          <p className="text-xs text-muted-foreground mt-1">
+            Reason: {lastFailure.failure_reason.replaceAll("_", " ")} —{" "}
+            {lastFailure.retryable
+              ? "retrying may succeed."
+              : "retrying will not help."}
+          </p>
+        )}
         {proposal.tx_id && (
           <p className="font-mono text-xs text-muted-foreground break-all mt-1">
             {proposal.tx_id}
           </p>
         )}
+        {feePaid && (
+          <p className="text-xs text-muted-foreground mt-1">
+            Fee paid: {feePaid} XRD
+          </p>
+        )}
         {proposal.submitted_at && (
           <p className="text-xs text-muted-foreground mt-1">
             Submitted{" "}
@@ -523,6 +542,20 @@ function ValidityWarning({ proposal }: { proposal: Proposal }) {
   );
 }
 
+function PreviewWarning({ proposal }: { proposal: Proposal }) {
+  return (
+    <Alert className="border-yellow-500/30 bg-yellow-500/5">
+      <AlertTitle className="text-yellow-400">
+        Preview {proposal.preview_status} at creation
+      </AlertTitle>
+      <AlertDescription className="break-all">
+        {proposal.preview_error ??
+          "This proposal is likely to fail when submitted."}
+      </AlertDescription>
+    </Alert>
+  );
+}
+
 // eslint-disable-next-line @typescript-eslint/no-explicit-any
 type PreviewResult = { receipt: any; resource_changes: any[]; logs: any[] };
 
@@ -1,6 +1,7 @@
 import { createFileRoute, useNavigate } from "@tanstack/react-router";
 import { Result, useAtomSet, useAtomValue } from "@effect-atom/atom-react";
 import { createProposalAtom } from "@/atom/proposalAtoms";
+import type { FeeMode } from "@/atom/orchestratorClient";
 import { epochDurationAtom } from "@/atom/gatewayAtoms";
 import { formatEpochDelta, hoursToEpochs } from "@/lib/epochTime";
 import { ClientOnly } from "@/lib/ClientOnly";
@@ -52,6 +53,7 @@ function CreateProposalForm() {
   const [expiryMode, setExpiryMode] = useState<"hours" | "epoch">("hours");
   const [expiryHours, setExpiryHours] = useState("");
   const [expiryEpoch, setExpiryEpoch] = useState("");
+  const [feeMode, setFeeMode] = useState<FeeMode>("fee_payer");
   const [error, setError] = useState<string | null>(null);
   const [submitting, setSubmitting] = useState(false);
 
@@ -105,6 +107,7 @@ function CreateProposalForm() {
       const proposal = await createProposal({
         manifest_text: manifestText,
         expiry_epoch: epoch,
+        fee_mode: feeMode,
       });
       navigate({ to: "/proposals/$id", params: { id: proposal.id } });
     } catch (err) {
@@ -236,6 +239,48 @@ function CreateProposalForm() {
         </p>
       </div>
 
+      <div className="space-y-2">
+        <Label>Transaction Fee</Label>
+        <div className="flex gap-1">
+          <Button
+            type="button"
+            size="sm"
+            variant={feeMode === "fee_payer" ? "accent" : "ghost"}
+            onClick={() => setFeeMode("fee_payer")}
+          >
+            Server Fee Payer
+          </Button>
+          <Button
+            type="button"
+            size="sm"
+            variant={feeMode === "multisig" ? "accent" : "ghost"}
+            onClick={() => setFeeMode("multisig")}
+          >
+            Multisig Account
+          </Button>
+          <Button
+            type="button"
+            size="sm"
+            variant={feeMode === "reimburse_fee_payer" ? "accent" : "ghost"}
+            onClick={() => setFeeMode("reimburse_fee_payer")}
+          >
+            Reimburse Fee Payer
+          </Button>
+        </div>
+        {feeMode === "multisig" && (
+          <p className="text-xs text-muted-foreground">
+            A lock_fee on the multisig account is added to the start of the
+            manifest. Only the fee actually used is charged.
+          </p>
+        )}
+        {feeMode === "reimburse_fee_payer" && (
+          <p className="text-xs text-muted-foreground">
+            The server fee payer pays the fee, and the manifest pays it back
+            from the multisig account with the previewed fee plus a margin.
+          </p>
+        )}
+      </div>
+
       {error && (
         <Alert variant="destructive">
           <AlertDescription>{error}</AlertDescription>
@@ -0,0 +1,3 @@
+-- Record the curve of each collected signature so reconstruction can rebuild
+-- Ed25519 and Secp256k1 entries. Existing rows were all Ed25519.
+ALTER TABLE signatures ADD COLUMN signer_key_type TEXT NOT NULL DEFAULT 'EddsaEd25519';
@@ -0,0 +1,10 @@
+-- Allow proposals to be withdrawn before submission
+ALTER TYPE proposal_status ADD VALUE 'cancelled';
+
+-- Key hash of the proposer (optional), allowed to cancel alongside current signers
+ALTER TABLE proposals ADD COLUMN proposer_key_hash TEXT;
+
+-- Who cancelled the proposal, why, and when
+ALTER TABLE proposals ADD COLUMN cancelled_by TEXT;
+ALTER TABLE proposals ADD COLUMN cancel_reason TEXT;
+ALTER TABLE proposals ADD COLUMN cancelled_at TIMESTAMPTZ;
@@ -0,0 +1,6 @@
+-- Persist the notarized transaction so the submission worker can resume an
+-- attempt after a restart, and track when each attempt last changed.
+ALTER TABLE submission_attempts ADD COLUMN notarized_transaction_hex TEXT;
+ALTER TABLE submission_attempts ADD COLUMN updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+CREATE INDEX idx_submission_attempts_status ON submission_attempts(status);
@@ -0,0 +1,2 @@
+-- Opt-in: submit automatically as soon as the proposal becomes Ready
+ALTER TABLE proposals ADD COLUMN auto_submit BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,2 @@
+-- Typed reason for failed submission attempts (see gateway::FailureReason)
+ALTER TABLE submission_attempts ADD COLUMN failure_reason TEXT;
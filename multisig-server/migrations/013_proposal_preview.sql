@@ -0,0 +1,5 @@
+-- Outcome of simulating the proposal via the Gateway preview at creation time
+ALTER TABLE proposals ADD COLUMN preview_status TEXT;
+ALTER TABLE proposals ADD COLUMN preview_error TEXT;
+ALTER TABLE proposals ADD COLUMN preview_fee_estimate TEXT;
+ALTER TABLE proposals ADD COLUMN preview_balance_changes JSONB;
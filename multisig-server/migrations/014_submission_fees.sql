@@ -0,0 +1,5 @@
+-- Fee estimated by previewing the composed transaction, the amount locked
+-- for it, and the fee actually paid according to the commit receipt
+ALTER TABLE submission_attempts ADD COLUMN fee_estimate TEXT;
+ALTER TABLE submission_attempts ADD COLUMN fee_locked TEXT;
+ALTER TABLE submission_attempts ADD COLUMN fee_paid TEXT;
@@ -0,0 +1,3 @@
+-- Fee payers are picked per submission from a pool; attempts that fail before
+-- a fee payer was chosen have none
+ALTER TABLE submission_attempts ALTER COLUMN fee_payer_account DROP NOT NULL;
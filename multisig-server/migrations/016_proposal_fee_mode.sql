@@ -0,0 +1,3 @@
+-- Who pays the fee: the server's fee payer (lock_fee in the main intent) or
+-- the multisig account itself (lock_fee injected into the subintent)
+ALTER TABLE proposals ADD COLUMN fee_mode TEXT NOT NULL DEFAULT 'fee_payer';
@@ -0,0 +1,4 @@
+-- Fee payer reimbursed by the subintent (fee_mode = 'reimburse_fee_payer'),
+-- and the XRD amount it is paid back
+ALTER TABLE proposals ADD COLUMN reimbursed_fee_payer TEXT;
+ALTER TABLE proposals ADD COLUMN fee_reimbursement TEXT;
@@ -0,0 +1,3 @@
+-- Set when the signed partial transaction was exported for submission by a
+-- third party; the server then only watches for the subintent's commit
+ALTER TABLE proposals ADD COLUMN externally_submitted_at TIMESTAMPTZ;
@@ -0,0 +1,12 @@
+-- Decisions the validity monitor made about a proposal's signatures and
+-- readiness, and why
+CREATE TABLE validity_decisions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    proposal_id UUID NOT NULL REFERENCES proposals(id),
+    decision TEXT NOT NULL,
+    signer_key_hash TEXT,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_validity_decisions_proposal ON validity_decisions(proposal_id);
@@ -0,0 +1,3 @@
+-- Epoch from which an attempt's transaction can no longer commit, so an
+-- attempt still pending on the Gateway is only failed once it has passed
+ALTER TABLE submission_attempts ADD COLUMN end_epoch_exclusive BIGINT;
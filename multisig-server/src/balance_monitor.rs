@@ -0,0 +1,262 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use radix_common::math::{CheckedDiv, Decimal};
+use serde::Serialize;
+use tokio::sync::RwLock;
+use utoipa::ToSchema;
+
+use crate::fee_payer::{FeePayerBalance, FeePayerPool};
+use crate::proposal_store::ProposalStore;
+
+/// Recent committed attempts averaged to estimate the fee of one submission.
+const FEE_SAMPLE_SIZE: i64 = 20;
+
+/// How long a low-balance webhook may take before it is abandoned.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// When a fee payer counts as running low.
+#[derive(Debug, Clone)]
+pub struct LowBalancePolicy {
+    /// Warn when the available XRD falls below this amount.
+    pub min_balance: Decimal,
+    /// Warn when the available XRD covers fewer than this many submissions.
+    pub min_submissions: u32,
+    /// Per-submission fee assumed until submissions have recorded real fees.
+    pub default_fee_estimate: Decimal,
+    /// Optional URL that receives a JSON POST when a fee payer becomes low.
+    pub webhook_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FeePayerStatus {
+    pub account: String,
+    /// XRD balance on ledger.
+    pub balance: String,
+    /// XRD locked by pending or submitted attempts.
+    pub reserved: String,
+    /// `balance - reserved`.
+    pub available: String,
+    /// How many more submissions `available` covers at the estimated fee.
+    pub estimated_submissions_remaining: u64,
+    pub low: bool,
+}
+
+/// Latest balance check of every fee payer.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FeePayerReport {
+    pub checked_at: DateTime<Utc>,
+    /// XRD fee assumed per submission: the recent average paid, or the configured default.
+    pub estimated_fee_per_submission: String,
+    pub fee_payers: Vec<FeePayerStatus>,
+}
+
+impl FeePayerReport {
+    pub fn any_low(&self) -> bool {
+        self.fee_payers.iter().any(|payer| payer.low)
+    }
+}
+
+/// Background task that periodically reads fee-payer balances through the
+/// Gateway and warns when any of them runs low.
+pub struct BalanceMonitor {
+    fee_payers: Arc<FeePayerPool>,
+    proposal_store: Arc<ProposalStore>,
+    policy: LowBalancePolicy,
+    http: reqwest::Client,
+    latest: RwLock<Option<FeePayerReport>>,
+}
+
+impl BalanceMonitor {
+    pub fn new(
+        fee_payers: Arc<FeePayerPool>,
+        proposal_store: Arc<ProposalStore>,
+        policy: LowBalancePolicy,
+    ) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .connect_timeout(WEBHOOK_TIMEOUT)
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .context("Failed to build webhook HTTP client")?;
+
+        Ok(Self {
+            fee_payers,
+            proposal_store,
+            policy,
+            http,
+            latest: RwLock::new(None),
+        })
+    }
+
+    /// The most recent report, if a check has completed yet.
+    pub async fn latest(&self) -> Option<FeePayerReport> {
+        self.latest.read().await.clone()
+    }
+
+    /// Check balances now and store the report.
+    pub async fn check(&self) -> Result<FeePayerReport> {
+        let balances = self.fee_payers.balances().await?;
+        let fee_estimate = match self
+            .proposal_store
+            .recent_average_fee_paid(FEE_SAMPLE_SIZE)
+            .await?
+            .and_then(|average| average.parse::<Decimal>().ok())
+        {
+            Some(average) if average.is_positive() => average,
+            _ => self.policy.default_fee_estimate,
+        };
+
+        let report = FeePayerReport {
+            checked_at: Utc::now(),
+            estimated_fee_per_submission: fee_estimate.to_string(),
+            fee_payers: balances
+                .iter()
+                .map(|balance| assess(balance, fee_estimate, &self.policy))
+                .collect(),
+        };
+
+        // Store the report before alerting. Alert only when a payer becomes
+        // low, not on every pass while it stays low
+        let previous = self.latest.write().await.replace(report.clone());
+        for status in report.fee_payers.iter().filter(|status| status.low) {
+            tracing::warn!(
+                "Fee payer {} is low on XRD: {} available (~{} submissions left)",
+                status.account,
+                status.available,
+                status.estimated_submissions_remaining
+            );
+            let was_low = previous.as_ref().is_some_and(|previous| {
+                previous
+                    .fee_payers
+                    .iter()
+                    .any(|p| p.account == status.account && p.low)
+            });
+            if !was_low {
+                self.notify(status);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Post the webhook in the background so a slow receiver can't hold up
+    /// the balance checks.
+    fn notify(&self, status: &FeePayerStatus) {
+        let Some(url) = self.policy.webhook_url.clone() else {
+            return;
+        };
+        let body = serde_json::json!({
+            "event": "fee_payer_low_balance",
+            "fee_payer": status,
+        });
+        tokio::spawn(post_webhook(self.http.clone(), url, body));
+    }
+}
+
+async fn post_webhook(http: reqwest::Client, url: String, body: serde_json::Value) {
+    match http.post(&url).json(&body).send().await {
+        Ok(response) if !response.status().is_success() => {
+            tracing::warn!("Low-balance webhook returned {}", response.status());
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to send low-balance webhook: {e}"),
+    }
+}
+
+fn assess(
+    balance: &FeePayerBalance,
+    fee_estimate: Decimal,
+    policy: &LowBalancePolicy,
+) -> FeePayerStatus {
+    let available = balance.available();
+    let estimated_submissions_remaining = if available.is_positive() && fee_estimate.is_positive() {
+        // Overflows only for a vanishingly small fee: effectively unlimited
+        available
+            .checked_div(fee_estimate)
+            .and_then(|d| d.checked_floor())
+            .and_then(|d| u64::try_from(d).ok())
+            .unwrap_or(u64::MAX)
+    } else {
+        0
+    };
+
+    FeePayerStatus {
+        account: balance.account.clone(),
+        balance: balance.balance.to_string(),
+        reserved: balance.reserved.to_string(),
+        available: available.to_string(),
+        estimated_submissions_remaining,
+        low: available < policy.min_balance
+            || estimated_submissions_remaining < u64::from(policy.min_submissions),
+    }
+}
+
+/// Run balance checks every `interval_secs`, starting immediately so
+/// `/health` has a report soon after startup.
+pub async fn run(monitor: Arc<BalanceMonitor>, interval_secs: u64) {
+    let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = monitor.check().await {
+            tracing::error!("Balance monitor error: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> LowBalancePolicy {
+        LowBalancePolicy {
+            min_balance: Decimal::from(50u32),
+            min_submissions: 10,
+            default_fee_estimate: Decimal::from(1u32),
+            webhook_url: None,
+        }
+    }
+
+    fn balance(balance: u32, reserved: u32) -> FeePayerBalance {
+        FeePayerBalance {
+            account: "account_tdx_2_test".into(),
+            balance: Decimal::from(balance),
+            reserved: Decimal::from(reserved),
+        }
+    }
+
+    #[test]
+    fn healthy_balance_is_not_low() {
+        let status = assess(&balance(200, 20), Decimal::from(2u32), &policy());
+        assert_eq!(status.available, "180");
+        assert_eq!(status.estimated_submissions_remaining, 90);
+        assert!(!status.low);
+    }
+
+    #[test]
+    fn below_threshold_is_low() {
+        let status = assess(&balance(60, 20), "0.5".parse().unwrap(), &policy());
+        assert_eq!(status.estimated_submissions_remaining, 80);
+        assert!(status.low);
+    }
+
+    #[test]
+    fn tiny_fee_does_not_overflow() {
+        let status = assess(
+            &balance(1_000_000, 0),
+            "0.000000000000000001".parse().unwrap(),
+            &policy(),
+        );
+        assert_eq!(status.estimated_submissions_remaining, u64::MAX);
+        assert!(!status.low);
+    }
+
+    #[test]
+    fn too_few_submissions_left_is_low() {
+        let status = assess(&balance(100, 0), "12.5".parse().unwrap(), &policy());
+        assert_eq!(status.estimated_submissions_remaining, 8);
+        assert!(status.low);
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use radix_common::prelude::*;
+
+use crate::gateway::GatewayClient;
+use crate::notary_signer::NotarySigner;
+use crate::proposal_store::ProposalStore;
+use crate::transaction_builder;
+
+/// One server key that pays transaction fees from its preallocated account,
+/// and notarizes those transactions unless a dedicated notary is configured.
+pub struct FeePayer {
+    pub signer: Arc<dyn NotarySigner>,
+    /// Bech32-encoded preallocated account address of the key.
+    pub account: String,
+}
+
+impl FeePayer {
+    pub fn new(signer: Arc<dyn NotarySigner>, network_id: u8) -> Result<Self> {
+        let network = transaction_builder::network_definition(network_id)?;
+        let address = ComponentAddress::preallocated_account_from_public_key(&signer.public_key());
+        let account = AddressBech32Encoder::new(&network)
+            .encode(address.as_bytes())
+            .map_err(|e| anyhow!("Failed to encode fee payer address: {e:?}"))?;
+
+        Ok(Self { signer, account })
+    }
+
+    pub fn component_address(&self) -> ComponentAddress {
+        ComponentAddress::preallocated_account_from_public_key(&self.signer.public_key())
+    }
+}
+
+/// XRD held by a fee payer, and how much of it in-flight submissions have locked.
+#[derive(Debug, Clone)]
+pub struct FeePayerBalance {
+    pub account: String,
+    pub balance: Decimal,
+    /// Sum of the fee locks of this payer's pending and submitted attempts.
+    pub reserved: Decimal,
+}
+
+impl FeePayerBalance {
+    /// XRD not already spoken for by in-flight submissions.
+    pub fn available(&self) -> Decimal {
+        self.balance - self.reserved
+    }
+}
+
+/// The configured fee-payer keys. Each submission uses whichever key has the
+/// most XRD available, so one empty account doesn't stop submissions and
+/// concurrent submissions spread over the pool.
+pub struct FeePayerPool {
+    payers: Vec<FeePayer>,
+    xrd_address: String,
+    gateway: Arc<GatewayClient>,
+    proposal_store: Arc<ProposalStore>,
+}
+
+impl FeePayerPool {
+    pub fn new(
+        payers: Vec<FeePayer>,
+        network_id: u8,
+        gateway: Arc<GatewayClient>,
+        proposal_store: Arc<ProposalStore>,
+    ) -> Result<Self> {
+        if payers.is_empty() {
+            return Err(anyhow!("At least one fee payer key is required"));
+        }
+        let network = transaction_builder::network_definition(network_id)?;
+        let xrd_address = AddressBech32Encoder::new(&network)
+            .encode(XRD.as_bytes())
+            .map_err(|e| anyhow!("Failed to encode XRD address: {e:?}"))?;
+
+        Ok(Self {
+            payers,
+            xrd_address,
+            gateway,
+            proposal_store,
+        })
+    }
+
+    /// Any configured fee payer, for work that needs a key but no XRD.
+    pub fn any(&self) -> &FeePayer {
+        &self.payers[0]
+    }
+
+    /// Current XRD balance and in-flight reservations of every fee payer.
+    pub async fn balances(&self) -> Result<Vec<FeePayerBalance>> {
+        let accounts: Vec<String> = self.payers.iter().map(|p| p.account.clone()).collect();
+        let balances = self
+            .gateway
+            .get_fungible_balances(&accounts, &self.xrd_address)
+            .await?;
+
+        let mut reserved: HashMap<String, Decimal> = HashMap::new();
+        for attempt in self
+            .proposal_store
+            .list_in_flight_submission_attempts()
+            .await?
+        {
+            let (Some(account), Some(locked)) = (attempt.fee_payer_account, attempt.fee_locked)
+            else {
+                continue;
+            };
+            if let Ok(locked) = locked.parse::<Decimal>() {
+                *reserved.entry(account).or_insert(Decimal::ZERO) += locked;
+            }
+        }
+
+        Ok(accounts
+            .into_iter()
+            .map(|account| FeePayerBalance {
+                balance: balances.get(&account).copied().unwrap_or(Decimal::ZERO),
+                reserved: reserved.get(&account).copied().unwrap_or(Decimal::ZERO),
+                account,
+            })
+            .collect())
+    }
+
+    /// The configured fee payer with `account`, and its balance.
+    pub async fn select_account(&self, account: &str) -> Result<(&FeePayer, FeePayerBalance)> {
+        let payer = self
+            .payers
+            .iter()
+            .find(|p| p.account == account)
+            .ok_or_else(|| anyhow!("Fee payer {account} is no longer configured"))?;
+        let balance = self
+            .balances()
+            .await?
+            .into_iter()
+            .find(|balance| balance.account == account)
+            .ok_or_else(|| anyhow!("No balance for fee payer {account}"))?;
+
+        Ok((payer, balance))
+    }
+
+    /// Pick the fee payer with the most available XRD.
+    pub async fn select(&self) -> Result<(&FeePayer, FeePayerBalance)> {
+        let healthiest = self
+            .balances()
+            .await?
+            .into_iter()
+            .max_by(|a, b| a.available().cmp(&b.available()))
+            .ok_or_else(|| anyhow!("No fee payers configured"))?;
+
+        let payer = self
+            .payers
+            .iter()
+            .find(|p| p.account == healthiest.account)
+            .ok_or_else(|| anyhow!("Unknown fee payer {}", healthiest.account))?;
+
+        Ok((payer, healthiest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notary_signer::LocalKeySigner;
+
+    #[test]
+    fn fee_payer_account_is_preallocated_from_signer_key() {
+        let signer = LocalKeySigner::from_hex(&"11".repeat(32)).unwrap();
+        let expected = ComponentAddress::preallocated_account_from_public_key(&signer.public_key());
+
+        let payer = FeePayer::new(Arc::new(signer), 2).unwrap();
+
+        assert!(payer.account.starts_with("account_tdx_2_"));
+        assert_eq!(payer.component_address(), expected);
+    }
+
+    #[test]
+    fn available_subtracts_reserved() {
+        let balance = FeePayerBalance {
+            account: "account_tdx_2_test".into(),
+            balance: Decimal::from(100u32),
+            reserved: Decimal::from(30u32),
+        };
+        assert_eq!(balance.available(), Decimal::from(70u32));
+    }
+}
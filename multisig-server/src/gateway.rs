@@ -1,7 +1,15 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
 use anyhow::{anyhow, Context, Result};
+use radix_common::math::Decimal;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use crate::gateway_endpoints::{EndpointPool, GatewayEndpointStatus, RetryPolicy};
+use crate::ttl_cache::TtlCache;
+
 /// Information about a signer extracted from the access rule.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 pub struct SignerInfo {
@@ -15,31 +23,369 @@ pub struct SignerInfo {
     pub badge_local_id: String,
 }
 
-/// Parsed access rule: signers and threshold.
+/// Parsed access rule: the full proof-rule tree plus a flattened view of it.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 pub struct AccessRuleInfo {
+    /// Every distinct signature requirement anywhere in the rule tree.
     pub signers: Vec<SignerInfo>,
+    /// Minimum number of signatures that can satisfy the rule (for display;
+    /// readiness is decided by evaluating `rule`).
     pub threshold: u8,
     pub is_updatable: bool,
+    pub rule: AccessRuleNode,
+}
+
+impl AccessRuleInfo {
+    /// Whether signatures from the given signer key hashes satisfy the rule.
+    pub fn is_satisfied_by<'a>(&self, key_hashes: impl IntoIterator<Item = &'a str>) -> bool {
+        let signed: HashSet<&str> = key_hashes.into_iter().collect();
+        self.rule.is_satisfied_by(&signed)
+    }
+
+    /// How many more signatures the rule needs on top of `key_hashes`: zero
+    /// once it is satisfied, otherwise the cheapest way to meet every unmet
+    /// branch. `None` if signatures alone can never satisfy it.
+    pub fn signatures_needed<'a>(
+        &self,
+        key_hashes: impl IntoIterator<Item = &'a str>,
+    ) -> Option<usize> {
+        let signed: HashSet<&str> = key_hashes.into_iter().collect();
+        self.rule.signatures_needed(&signed)
+    }
+
+    /// The smallest subset of `key_hashes` that satisfies the rule, or `None`
+    /// if they don't satisfy it at all. Keeps the order of `key_hashes`, and
+    /// no key hash can be dropped from the result without breaking the rule.
+    pub fn minimal_signers<'a>(&self, key_hashes: &[&'a str]) -> Option<Vec<&'a str>> {
+        if !self.is_satisfied_by(key_hashes.iter().copied()) {
+            return None;
+        }
+
+        // The cheapest branches first; shared signers across branches can
+        // break that selection, so fall back to everything available.
+        let available: HashSet<&str> = key_hashes.iter().copied().collect();
+        let mut selected: Vec<&'a str> = match self.rule.select_signers(&available) {
+            Some(chosen) => key_hashes
+                .iter()
+                .copied()
+                .filter(|hash| chosen.contains(hash))
+                .collect(),
+            None => key_hashes.to_vec(),
+        };
+        if !self.is_satisfied_by(selected.iter().copied()) {
+            selected = key_hashes.to_vec();
+        }
+
+        // Drop any signature the rule is still satisfied without, latest first
+        for i in (0..selected.len()).rev() {
+            let without = selected
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, hash)| *hash);
+            if self.is_satisfied_by(without) {
+                selected.remove(i);
+            }
+        }
+
+        Some(selected)
+    }
+
+    /// Build a flat N-of-M rule from a signer list (test fixtures).
+    #[cfg(test)]
+    pub fn count_of(signers: Vec<SignerInfo>, threshold: u8) -> Self {
+        let rule = AccessRuleNode::ProofRule {
+            proof_rule: ProofRule::CountOf {
+                count: threshold,
+                list: signers.iter().cloned().map(Requirement::Signer).collect(),
+            },
+        };
+        Self {
+            signers,
+            threshold,
+            is_updatable: true,
+            rule,
+        }
+    }
+}
+
+/// Node of an owner role's access rule tree, mirroring the Gateway's
+/// `AccessRuleNode` (composite requirement) shape.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(tag = "type")]
+pub enum AccessRuleNode {
+    AllowAll,
+    ProofRule {
+        proof_rule: ProofRule,
+    },
+    AnyOf {
+        #[schema(no_recursion)]
+        access_rules: Vec<AccessRuleNode>,
+    },
+    AllOf {
+        #[schema(no_recursion)]
+        access_rules: Vec<AccessRuleNode>,
+    },
+}
+
+/// Leaf proof rule (the Gateway's `ProofRule` / engine `BasicRequirement`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(tag = "type")]
+pub enum ProofRule {
+    Require { requirement: Requirement },
+    AmountOf { amount: String, resource: String },
+    CountOf { count: u8, list: Vec<Requirement> },
+    AllOf { list: Vec<Requirement> },
+    AnyOf { list: Vec<Requirement> },
+}
+
+/// A single requirement: a signature badge, another non-fungible badge, or a resource.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(tag = "type")]
+pub enum Requirement {
+    /// Virtual signature badge — satisfied by a signature from this key.
+    Signer(SignerInfo),
+    /// Regular non-fungible badge; cannot be satisfied by signatures alone.
+    NonFungible {
+        resource_address: String,
+        local_id: String,
+    },
+    /// Fungible or non-fungible resource proof; cannot be satisfied by signatures alone.
+    Resource { resource: String },
+}
+
+impl AccessRuleNode {
+    /// Evaluate the tree against a set of signer key hashes.
+    pub fn is_satisfied_by(&self, signed: &HashSet<&str>) -> bool {
+        match self {
+            AccessRuleNode::AllowAll => true,
+            AccessRuleNode::ProofRule { proof_rule } => proof_rule.is_satisfied_by(signed),
+            AccessRuleNode::AnyOf { access_rules } => {
+                access_rules.iter().any(|r| r.is_satisfied_by(signed))
+            }
+            AccessRuleNode::AllOf { access_rules } => {
+                access_rules.iter().all(|r| r.is_satisfied_by(signed))
+            }
+        }
+    }
+
+    /// Minimum number of signatures that satisfies the tree, or `None` if
+    /// signatures alone can never satisfy it. Assumes no signer appears in
+    /// more than one branch.
+    pub fn min_signatures(&self) -> Option<usize> {
+        match self {
+            AccessRuleNode::AllowAll => Some(0),
+            AccessRuleNode::ProofRule { proof_rule } => proof_rule.min_signatures(),
+            AccessRuleNode::AnyOf { access_rules } => {
+                access_rules.iter().filter_map(|r| r.min_signatures()).min()
+            }
+            AccessRuleNode::AllOf { access_rules } => {
+                access_rules.iter().map(|r| r.min_signatures()).sum()
+            }
+        }
+    }
+
+    /// Additional signatures needed on top of `signed`, summed over the unmet
+    /// branches of an `AllOf` and the cheapest branch of an `AnyOf`. `None`
+    /// if signatures alone can never satisfy the tree.
+    pub fn signatures_needed(&self, signed: &HashSet<&str>) -> Option<usize> {
+        match self {
+            AccessRuleNode::AllowAll => Some(0),
+            AccessRuleNode::ProofRule { proof_rule } => proof_rule.signatures_needed(signed),
+            AccessRuleNode::AnyOf { access_rules } => access_rules
+                .iter()
+                .filter_map(|r| r.signatures_needed(signed))
+                .min(),
+            AccessRuleNode::AllOf { access_rules } => access_rules
+                .iter()
+                .map(|r| r.signatures_needed(signed))
+                .sum(),
+        }
+    }
+
+    /// Pick as few of the `available` signers as satisfy the tree, taking
+    /// the cheapest branch of each `AnyOf`. `None` if they can't satisfy it.
+    fn select_signers<'a>(&'a self, available: &HashSet<&str>) -> Option<HashSet<&'a str>> {
+        match self {
+            AccessRuleNode::AllowAll => Some(HashSet::new()),
+            AccessRuleNode::ProofRule { proof_rule } => proof_rule.select_signers(available),
+            AccessRuleNode::AnyOf { access_rules } => access_rules
+                .iter()
+                .filter_map(|r| r.select_signers(available))
+                .min_by_key(HashSet::len),
+            AccessRuleNode::AllOf { access_rules } => {
+                access_rules
+                    .iter()
+                    .try_fold(HashSet::new(), |mut selected, r| {
+                        selected.extend(r.select_signers(available)?);
+                        Some(selected)
+                    })
+            }
+        }
+    }
+
+    /// Every signature requirement in the tree, de-duplicated by key hash.
+    pub fn signers(&self) -> Vec<SignerInfo> {
+        let mut signers = Vec::new();
+        self.collect_signers(&mut signers);
+        signers
+    }
+
+    fn collect_signers(&self, out: &mut Vec<SignerInfo>) {
+        match self {
+            AccessRuleNode::AllowAll => {}
+            AccessRuleNode::ProofRule { proof_rule } => {
+                for requirement in proof_rule.requirements() {
+                    if let Requirement::Signer(signer) = requirement {
+                        if !out.iter().any(|s| s.key_hash == signer.key_hash) {
+                            out.push(signer.clone());
+                        }
+                    }
+                }
+            }
+            AccessRuleNode::AnyOf { access_rules } | AccessRuleNode::AllOf { access_rules } => {
+                for rule in access_rules {
+                    rule.collect_signers(out);
+                }
+            }
+        }
+    }
 }
 
+impl ProofRule {
+    fn requirements(&self) -> &[Requirement] {
+        match self {
+            ProofRule::Require { requirement } => std::slice::from_ref(requirement),
+            ProofRule::AmountOf { .. } => &[],
+            ProofRule::CountOf { list, .. }
+            | ProofRule::AllOf { list }
+            | ProofRule::AnyOf { list } => list,
+        }
+    }
+
+    pub fn is_satisfied_by(&self, signed: &HashSet<&str>) -> bool {
+        match self {
+            ProofRule::Require { requirement } => requirement.is_satisfied_by(signed),
+            ProofRule::AmountOf { .. } => false,
+            ProofRule::CountOf { count, list } => {
+                list.iter().filter(|r| r.is_satisfied_by(signed)).count() >= *count as usize
+            }
+            ProofRule::AllOf { list } => list.iter().all(|r| r.is_satisfied_by(signed)),
+            ProofRule::AnyOf { list } => list.iter().any(|r| r.is_satisfied_by(signed)),
+        }
+    }
+
+    fn select_signers<'a>(&'a self, available: &HashSet<&str>) -> Option<HashSet<&'a str>> {
+        let present: Vec<&'a str> = self
+            .requirements()
+            .iter()
+            .filter_map(|r| match r {
+                Requirement::Signer(signer) if available.contains(signer.key_hash.as_str()) => {
+                    Some(signer.key_hash.as_str())
+                }
+                _ => None,
+            })
+            .collect();
+        let needed = match self {
+            ProofRule::Require { .. } | ProofRule::AnyOf { .. } => 1,
+            ProofRule::AmountOf { .. } => return None,
+            ProofRule::CountOf { count, .. } => *count as usize,
+            ProofRule::AllOf { list } => list.len(),
+        };
+        (present.len() >= needed).then(|| present.into_iter().take(needed).collect())
+    }
+
+    pub fn signatures_needed(&self, signed: &HashSet<&str>) -> Option<usize> {
+        let requirements = self.requirements();
+        let met = requirements
+            .iter()
+            .filter(|r| r.is_satisfied_by(signed))
+            .count();
+        let signable = requirements
+            .iter()
+            .filter(|r| matches!(r, Requirement::Signer(_)) && !r.is_satisfied_by(signed))
+            .count();
+        let needed = match self {
+            ProofRule::Require { .. } | ProofRule::AnyOf { .. } => 1,
+            ProofRule::AmountOf { .. } => return None,
+            ProofRule::CountOf { count, .. } => *count as usize,
+            ProofRule::AllOf { list } => list.len(),
+        };
+        let missing = needed.saturating_sub(met);
+        (signable >= missing).then_some(missing)
+    }
+
+    pub fn min_signatures(&self) -> Option<usize> {
+        let signable = self
+            .requirements()
+            .iter()
+            .filter(|r| matches!(r, Requirement::Signer(_)))
+            .count();
+        match self {
+            ProofRule::Require { .. } | ProofRule::AnyOf { .. } => (signable > 0).then_some(1),
+            ProofRule::AmountOf { .. } => None,
+            ProofRule::CountOf { count, .. } => {
+                (signable >= *count as usize).then_some(*count as usize)
+            }
+            ProofRule::AllOf { list } => (signable == list.len()).then_some(signable),
+        }
+    }
+}
+
+impl Requirement {
+    fn is_satisfied_by(&self, signed: &HashSet<&str>) -> bool {
+        matches!(self, Requirement::Signer(signer) if signed.contains(signer.key_hash.as_str()))
+    }
+}
+
+/// Most addresses the Gateway accepts in one `/state/entity/details` request.
+const ENTITY_DETAILS_MAX_ADDRESSES: usize = 20;
+
+/// How long access rules and the epoch are cached unless configured otherwise.
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// Timeouts used unless configured otherwise.
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
 pub struct GatewayClient {
     client: reqwest::Client,
-    base_url: String,
+    endpoints: EndpointPool,
+    retry: RetryPolicy,
+    /// Access rules by account address.
+    access_rules: TtlCache<String, AccessRuleInfo>,
+    epoch: TtlCache<(), u64>,
 }
 
 // --- Gateway API response types (subset needed for access rule parsing) ---
 
 #[derive(Debug, Deserialize)]
 struct EntityDetailsResponse {
+    #[serde(default)]
+    ledger_state: Option<LedgerState>,
     items: Vec<EntityDetailsItem>,
 }
 
 #[derive(Debug, Deserialize)]
 struct EntityDetailsItem {
+    #[serde(default)]
+    address: String,
+    fungible_resources: Option<FungibleResources>,
     details: Option<EntityDetails>,
 }
 
+#[derive(Debug, Deserialize)]
+struct FungibleResources {
+    items: Vec<FungibleResourceBalance>,
+}
+
+/// A fungible balance, as returned with the default (Global) aggregation level.
+#[derive(Debug, Deserialize)]
+struct FungibleResourceBalance {
+    resource_address: String,
+    amount: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct EntityDetails {
     role_assignments: Option<RoleAssignments>,
@@ -64,6 +410,8 @@ struct GatewayStatusResponse {
 #[derive(Debug, Deserialize)]
 struct LedgerState {
     epoch: u64,
+    #[serde(default)]
+    state_version: u64,
 }
 
 // --- Request types ---
@@ -73,87 +421,289 @@ struct EntityDetailsRequest {
     addresses: Vec<String>,
 }
 
+/// Which failed requests may be sent again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Retry {
+    /// Reads: retried on any endpoint.
+    Idempotent,
+    /// Transaction submission. Resending the same payload is only safe where
+    /// the Gateway's duplicate detection holds: once a request may have
+    /// reached an endpoint, retries stay on that endpoint, which reports the
+    /// resend as a duplicate. Requests that never connected fail over.
+    Submission,
+}
+
+/// Why one try of a request failed.
+struct TryError {
+    error: anyhow::Error,
+    /// Worth trying again (network errors, timeouts, 5xx, 429).
+    retryable: bool,
+    /// The request may have been received by the endpoint.
+    reached: bool,
+}
+
 impl GatewayClient {
+    /// A client for one Gateway with the default timeouts and retry policy.
     pub fn new(base_url: String) -> Self {
-        Self {
-            client: reqwest::Client::new(),
-            base_url,
+        Self::with_endpoints(vec![base_url]).expect("Gateway URL must not be empty")
+    }
+
+    /// A client for an ordered list of Gateways, failing over between them.
+    pub fn with_endpoints(base_urls: Vec<String>) -> Result<Self> {
+        Ok(Self {
+            client: http_client(DEFAULT_CONNECT_TIMEOUT, DEFAULT_REQUEST_TIMEOUT)?,
+            endpoints: EndpointPool::new(base_urls)?,
+            retry: RetryPolicy::default(),
+            access_rules: TtlCache::new(DEFAULT_CACHE_TTL),
+            epoch: TtlCache::new(DEFAULT_CACHE_TTL),
+        })
+    }
+
+    /// Give up connecting after `connect` and on a whole request after `request`.
+    pub fn with_timeouts(mut self, connect: Duration, request: Duration) -> Result<Self> {
+        self.client = http_client(connect, request)?;
+        Ok(self)
+    }
+
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Health of every configured Gateway endpoint, in configured order.
+    pub fn endpoint_status(&self) -> Vec<GatewayEndpointStatus> {
+        self.endpoints.status()
+    }
+
+    /// POST to a Gateway API path, retrying with backoff and failing over
+    /// between endpoints as `retry` allows. `what` names the call in errors.
+    async fn post<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: Option<&serde_json::Value>,
+        retry: Retry,
+        what: &str,
+    ) -> Result<T> {
+        let mut pinned: Option<usize> = None;
+        let mut last_error = None;
+
+        for attempt in 0..=self.retry.max_retries {
+            if attempt > 0 {
+                let delay = self.retry.backoff(attempt - 1);
+                tracing::warn!(
+                    "{what} failed, retrying in {delay:?} ({attempt}/{}): {}",
+                    self.retry.max_retries,
+                    last_error
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_default()
+                );
+                tokio::time::sleep(delay).await;
+            }
+
+            let order = match pinned {
+                Some(endpoint) => vec![endpoint],
+                None => self.endpoints.order(),
+            };
+            for endpoint in order {
+                match self.try_post(endpoint, path, body, what).await {
+                    Ok(value) => return Ok(value),
+                    Err(TryError {
+                        error,
+                        retryable,
+                        reached,
+                    }) => {
+                        if !retryable {
+                            return Err(error);
+                        }
+                        last_error = Some(error);
+                        if retry == Retry::Submission && reached {
+                            pinned = Some(endpoint);
+                            break;
+                        }
+                    }
+                }
+            }
         }
+
+        Err(last_error.unwrap_or_else(|| anyhow!("{what} failed: no Gateway endpoint tried")))
     }
 
-    /// Read the access rule (signers + threshold) for a multisig account.
-    pub async fn read_access_rule(&self, account_address: &str) -> Result<AccessRuleInfo> {
-        let url = format!("{}/state/entity/details", self.base_url);
-        let body = EntityDetailsRequest {
-            addresses: vec![account_address.to_string()],
-        };
+    /// One try of a request against one endpoint, recording its health.
+    async fn try_post<T: DeserializeOwned>(
+        &self,
+        endpoint: usize,
+        path: &str,
+        body: Option<&serde_json::Value>,
+        what: &str,
+    ) -> std::result::Result<T, TryError> {
+        let url = format!("{}{path}", self.endpoints.url(endpoint));
+        let mut request = self.client.post(&url);
+        if let Some(body) = body {
+            request = request.json(body);
+        }
 
-        let response = self
-            .client
-            .post(&url)
-            .json(&body)
-            .send()
-            .await
-            .context("Failed to send request to Gateway API")?;
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                let reached = !e.is_connect();
+                let error = anyhow!(e).context(format!("Failed to send {what} request to {url}"));
+                self.endpoints
+                    .record_failure(endpoint, &format!("{error:#}"));
+                return Err(TryError {
+                    error,
+                    retryable: true,
+                    reached,
+                });
+            }
+        };
 
-        if !response.status().is_success() {
-            let status = response.status();
+        let status = response.status();
+        if !status.is_success() {
             let error_text = response.text().await.unwrap_or_default();
-            return Err(anyhow!("Gateway API returned {status}: {error_text}"));
+            let error = anyhow!("{what} failed ({status}): {error_text}");
+            let retryable = status.is_server_error()
+                || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                || status == reqwest::StatusCode::REQUEST_TIMEOUT;
+            if retryable {
+                self.endpoints.record_failure(endpoint, &error.to_string());
+            } else {
+                // The endpoint is up; the request itself was refused
+                self.endpoints.record_success(endpoint);
+            }
+            return Err(TryError {
+                error,
+                retryable,
+                reached: true,
+            });
         }
 
-        let details: EntityDetailsResponse = response
-            .json()
-            .await
-            .context("Failed to parse Gateway API response")?;
+        self.endpoints.record_success(endpoint);
+        response.json().await.map_err(|e| TryError {
+            error: anyhow!(e).context(format!("Failed to parse {what} response")),
+            retryable: false,
+            reached: true,
+        })
+    }
 
-        let item = details
-            .items
-            .into_iter()
-            .next()
-            .ok_or_else(|| anyhow!("No items in entity details response"))?;
+    /// Cache access rules and the epoch for `ttl`; zero disables caching.
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.access_rules = TtlCache::new(ttl);
+        self.epoch = TtlCache::new(ttl);
+        self
+    }
 
-        let role_assignments = item
-            .details
-            .ok_or_else(|| anyhow!("No details in entity response"))?
-            .role_assignments
-            .ok_or_else(|| anyhow!("No role_assignments in entity details"))?;
+    /// Read the access rule (signers + threshold) for a multisig account.
+    pub async fn read_access_rule(&self, account_address: &str) -> Result<AccessRuleInfo> {
+        self.read_access_rules(&[account_address])
+            .await?
+            .remove(account_address)
+            .ok_or_else(|| anyhow!("No items in entity details response"))?
+    }
 
-        let owner = role_assignments
-            .owner
-            .ok_or_else(|| anyhow!("No owner in role_assignments"))?;
+    /// Read the access rules of several accounts, batching Gateway requests
+    /// and serving recently read rules from the cache.
+    ///
+    /// Fails only if a request fails; an account whose rule can't be parsed
+    /// gets its own error in the returned map.
+    pub async fn read_access_rules(
+        &self,
+        account_addresses: &[&str],
+    ) -> Result<HashMap<String, Result<AccessRuleInfo>>> {
+        let mut rules = HashMap::new();
+        let mut missing = Vec::new();
+        for &address in account_addresses {
+            match self.access_rules.get(address) {
+                Some(rule) => {
+                    rules.insert(address.to_string(), Ok(rule));
+                }
+                None if !missing.contains(&address) => missing.push(address),
+                None => {}
+            }
+        }
 
-        let is_updatable = owner.updater.as_deref() == Some("Owner");
+        for chunk in missing.chunks(ENTITY_DETAILS_MAX_ADDRESSES) {
+            let details = self.entity_details(chunk).await?;
+            let state_version = details
+                .ledger_state
+                .as_ref()
+                .map_or(0, |ledger_state| ledger_state.state_version);
+            for item in details.items {
+                let address = item.address.clone();
+                let rule = access_rule_from_item(item);
+                if let Ok(rule) = &rule {
+                    self.access_rules
+                        .insert(address.clone(), rule.clone(), state_version);
+                }
+                rules.insert(address, rule);
+            }
+        }
 
-        let mut info = parse_access_rule(&owner.rule)?;
-        info.is_updatable = is_updatable;
-        Ok(info)
+        Ok(rules)
     }
 
-    /// Submit a notarized transaction to the network.
-    pub async fn submit_transaction(&self, notarized_transaction_hex: &str) -> Result<bool> {
-        let url = format!("{}/transaction/submit", self.base_url);
-
-        let response = self
-            .client
-            .post(&url)
-            .json(&serde_json::json!({
-                "notarized_transaction_hex": notarized_transaction_hex,
-            }))
-            .send()
-            .await
-            .context("Failed to submit transaction")?;
+    /// Forget a cached access rule, e.g. after a transaction that may have
+    /// changed it committed.
+    pub fn invalidate_access_rule(&self, account_address: &str) {
+        self.access_rules.invalidate(account_address);
+    }
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(anyhow!("Submit failed ({status}): {error_text}"));
+    async fn entity_details(&self, addresses: &[&str]) -> Result<EntityDetailsResponse> {
+        let body = serde_json::to_value(EntityDetailsRequest {
+            addresses: addresses.iter().map(|a| a.to_string()).collect(),
+        })?;
+        self.post(
+            "/state/entity/details",
+            Some(&body),
+            Retry::Idempotent,
+            "Entity details query",
+        )
+        .await
+    }
+
+    /// Read the balance of one fungible resource for several accounts at once.
+    ///
+    /// Accounts that hold none of the resource (including preallocated
+    /// accounts that were never funded) get a zero balance.
+    pub async fn get_fungible_balances(
+        &self,
+        account_addresses: &[String],
+        resource_address: &str,
+    ) -> Result<HashMap<String, Decimal>> {
+        let addresses: Vec<&str> = account_addresses.iter().map(String::as_str).collect();
+        let details = self.entity_details(&addresses).await?;
+
+        let mut balances: HashMap<String, Decimal> = account_addresses
+            .iter()
+            .map(|address| (address.clone(), Decimal::ZERO))
+            .collect();
+        for item in details.items {
+            let amount = item
+                .fungible_resources
+                .into_iter()
+                .flat_map(|resources| resources.items)
+                .find(|balance| balance.resource_address == resource_address)
+                .map(|balance| parse_decimal(&balance.amount))
+                .transpose()?
+                .unwrap_or(Decimal::ZERO);
+            balances.insert(item.address, amount);
         }
 
-        let result: SubmitTransactionResponse = response
-            .json()
-            .await
-            .context("Failed to parse submit response")?;
+        Ok(balances)
+    }
+
+    /// Submit a notarized transaction to the network.
+    pub async fn submit_transaction(&self, notarized_transaction_hex: &str) -> Result<bool> {
+        let result: SubmitTransactionResponse = self
+            .post(
+                "/transaction/submit",
+                Some(&serde_json::json!({
+                    "notarized_transaction_hex": notarized_transaction_hex,
+                })),
+                Retry::Submission,
+                "Submit",
+            )
+            .await?;
 
         Ok(result.duplicate)
     }
@@ -163,52 +713,95 @@ impl GatewayClient {
         &self,
         intent_hash: &str,
     ) -> Result<TransactionStatusResponse> {
-        let url = format!("{}/transaction/status", self.base_url);
-
-        let response = self
-            .client
-            .post(&url)
-            .json(&serde_json::json!({
+        self.post(
+            "/transaction/status",
+            Some(&serde_json::json!({
                 "intent_hash": intent_hash,
-            }))
-            .send()
-            .await
-            .context("Failed to query transaction status")?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(anyhow!("Status query failed ({status}): {error_text}"));
-        }
+            })),
+            Retry::Idempotent,
+            "Status query",
+        )
+        .await
+    }
 
-        let status: TransactionStatusResponse = response
-            .json()
-            .await
-            .context("Failed to parse transaction status")?;
+    /// Whether a subintent has been committed on ledger, by any transaction.
+    pub async fn get_subintent_status(&self, subintent_hash: &str) -> Result<SubintentStatus> {
+        self.post(
+            "/transaction/subintent-status",
+            Some(&serde_json::json!({
+                "subintent_hash": subintent_hash,
+            })),
+            Retry::Idempotent,
+            "Subintent status query",
+        )
+        .await
+    }
 
-        Ok(status)
+    /// Fetch the receipt and fee paid of a committed transaction by intent hash.
+    pub async fn get_committed_transaction(
+        &self,
+        intent_hash: &str,
+    ) -> Result<CommittedTransaction> {
+        let details: CommittedDetailsResponse = self
+            .post(
+                "/transaction/committed-details",
+                Some(&serde_json::json!({
+                    "intent_hash": intent_hash,
+                })),
+                Retry::Idempotent,
+                "Committed details query",
+            )
+            .await?;
+
+        Ok(details.transaction)
     }
 
-    /// Poll until a transaction is committed or fails.
+    /// Poll until a transaction is committed or fails, at most `max_attempts` times.
     ///
-    /// Returns the final status string ("CommittedSuccess") or an error.
-    pub async fn wait_for_commit(&self, intent_hash: &str, max_attempts: u32) -> Result<String> {
+    /// A failed commit or a rejection comes back as [`CommitOutcome::Failed`]
+    /// with a reason classified from the committed receipt (for
+    /// `CommittedFailure`) or the status response (for `Rejected`). A
+    /// transaction still pending when the polls run out is
+    /// [`CommitOutcome::Pending`]. Errors are reserved for failing to talk to
+    /// the Gateway.
+    pub async fn wait_for_commit(
+        &self,
+        intent_hash: &str,
+        max_attempts: u32,
+    ) -> Result<CommitOutcome> {
         for attempt in 0..max_attempts {
             let status = self.get_transaction_status(intent_hash).await?;
 
             match status.status.as_str() {
-                "CommittedSuccess" => return Ok("CommittedSuccess".to_string()),
+                "CommittedSuccess" => {
+                    let fee_paid = self
+                        .committed_transaction_or_warn(intent_hash)
+                        .await
+                        .and_then(|tx| tx.fee_paid());
+                    return Ok(CommitOutcome::Committed { fee_paid });
+                }
                 "CommittedFailure" => {
-                    return Err(anyhow!(
-                        "Transaction failed: {}",
-                        status.error_message.unwrap_or_default()
-                    ));
+                    // The receipt carries the engine error; fall back to the status detail
+                    let committed = self.committed_transaction_or_warn(intent_hash).await;
+                    let fee_paid = committed.as_ref().and_then(CommittedTransaction::fee_paid);
+                    let message = committed
+                        .and_then(|tx| tx.receipt.error_message)
+                        .or_else(|| status.error_detail().map(str::to_string))
+                        .unwrap_or_default();
+
+                    return Ok(CommitOutcome::Failed {
+                        reason: FailureReason::classify(&message, FailureReason::ExecutionFailed),
+                        message: format!("Transaction failed: {message}"),
+                        fee_paid,
+                    });
                 }
                 "Rejected" => {
-                    return Err(anyhow!(
-                        "Transaction rejected: {}",
-                        status.error_message.unwrap_or_default()
-                    ));
+                    let message = status.error_detail().unwrap_or_default().to_string();
+                    return Ok(CommitOutcome::Failed {
+                        reason: FailureReason::classify(&message, FailureReason::Rejected),
+                        message: format!("Transaction rejected: {message}"),
+                        fee_paid: None,
+                    });
                 }
                 "Pending" | "Unknown" => {
                     if attempt < max_attempts - 1 {
@@ -220,37 +813,106 @@ impl GatewayClient {
                 }
             }
         }
-        Err(anyhow!(
-            "Timeout waiting for commit after {max_attempts} attempts"
-        ))
+        Ok(CommitOutcome::Pending)
     }
 
-    /// Get the current epoch from the Gateway.
-    pub async fn get_current_epoch(&self) -> Result<u64> {
-        let url = format!("{}/status/gateway-status", self.base_url);
-
-        let response = self
-            .client
-            .post(&url)
-            .send()
+    /// Committed details are a nice-to-have once the status is known.
+    async fn committed_transaction_or_warn(
+        &self,
+        intent_hash: &str,
+    ) -> Option<CommittedTransaction> {
+        self.get_committed_transaction(intent_hash)
             .await
-            .context("Failed to send request to Gateway API")?;
+            .inspect_err(|e| tracing::warn!("Failed to fetch receipt for {intent_hash}: {e}"))
+            .ok()
+    }
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(anyhow!("Gateway API returned {status}: {error_text}"));
-        }
+    /// Simulate a transaction via `transaction/preview-v2`.
+    ///
+    /// Runs with free credit and all signature proofs assumed, so only the
+    /// manifest itself (balances, method names, ...) decides the outcome.
+    pub async fn preview_transaction_v2(
+        &self,
+        preview_transaction_hex: &str,
+    ) -> Result<PreviewOutcome> {
+        let preview: PreviewV2Response = self
+            .post(
+                "/transaction/preview-v2",
+                Some(&serde_json::json!({
+                    "preview_transaction": {
+                        "type": "Compiled",
+                        "preview_transaction_hex": preview_transaction_hex,
+                    },
+                    "flags": {
+                        "use_free_credit": true,
+                        "assume_all_signature_proofs": true,
+                        "skip_epoch_check": false,
+                        "disable_auth_checks": false,
+                    },
+                    "opt_ins": {
+                        "core_api_receipt": true,
+                    },
+                })),
+                Retry::Idempotent,
+                "Preview",
+            )
+            .await?;
+
+        let receipt = preview
+            .core_api_receipt
+            .ok_or_else(|| anyhow!("Preview response has no core_api_receipt"))?;
+        PreviewOutcome::from_core_receipt(&receipt)
+    }
 
-        let status: GatewayStatusResponse = response
-            .json()
-            .await
-            .context("Failed to parse gateway status response")?;
+    /// Get the current epoch from the Gateway, or the cache if read recently.
+    pub async fn get_current_epoch(&self) -> Result<u64> {
+        if let Some(epoch) = self.epoch.get(&()) {
+            return Ok(epoch);
+        }
 
-        Ok(status.ledger_state.epoch)
+        let status: GatewayStatusResponse = self
+            .post(
+                "/status/gateway-status",
+                None,
+                Retry::Idempotent,
+                "Gateway status query",
+            )
+            .await?;
+
+        let ledger_state = status.ledger_state;
+        self.epoch
+            .insert((), ledger_state.epoch, ledger_state.state_version);
+        Ok(ledger_state.epoch)
     }
 }
 
+fn http_client(connect_timeout: Duration, request_timeout: Duration) -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .connect_timeout(connect_timeout)
+        .timeout(request_timeout)
+        .build()
+        .context("Failed to build Gateway HTTP client")
+}
+
+/// Extract an account's owner access rule from its entity details.
+fn access_rule_from_item(item: EntityDetailsItem) -> Result<AccessRuleInfo> {
+    let role_assignments = item
+        .details
+        .ok_or_else(|| anyhow!("No details in entity response"))?
+        .role_assignments
+        .ok_or_else(|| anyhow!("No role_assignments in entity details"))?;
+
+    let owner = role_assignments
+        .owner
+        .ok_or_else(|| anyhow!("No owner in role_assignments"))?;
+
+    let is_updatable = owner.updater.as_deref() == Some("Owner");
+
+    let mut info = parse_access_rule(&owner.rule)?;
+    info.is_updatable = is_updatable;
+    Ok(info)
+}
+
 /// Parse the owner rule JSON into AccessRuleInfo.
 ///
 /// Expected structure for a multisig (CountOf) account:
@@ -270,129 +932,141 @@ impl GatewayClient {
 /// }
 /// ```
 ///
-/// Also handles single-signer (Require) accounts:
+/// `access_rule` may also be a composite node nesting further rules, e.g.
+/// "2 of council AND 1 of auditors":
 /// ```json
 /// {
-///   "type": "Protected",
-///   "access_rule": {
-///     "type": "ProofRule",
-///     "proof_rule": {
-///       "type": "Require",
-///       "requirement": { "type": "NonFungible", "non_fungible": { ... } }
-///     }
-///   }
+///   "type": "AllOf",
+///   "access_rules": [
+///     { "type": "ProofRule", "proof_rule": { "type": "CountOf", "count": 2, "list": [ ... ] } },
+///     { "type": "ProofRule", "proof_rule": { "type": "CountOf", "count": 1, "list": [ ... ] } }
+///   ]
 /// }
 /// ```
+///
+/// Fails if the rule can never be satisfied by signatures alone.
 fn parse_access_rule(rule_json: &serde_json::Value) -> Result<AccessRuleInfo> {
     let rule_type = rule_json["type"]
         .as_str()
         .ok_or_else(|| anyhow!("Missing 'type' in owner rule"))?;
 
-    match rule_type {
-        "Protected" => {
-            let proof_rule = &rule_json["access_rule"]["proof_rule"];
-            let proof_rule_type = proof_rule["type"]
-                .as_str()
-                .ok_or_else(|| anyhow!("Missing 'type' in proof_rule"))?;
-
-            match proof_rule_type {
-                "CountOf" => parse_count_of(proof_rule),
-                "Require" => parse_require(proof_rule),
-                "AllOf" => parse_all_of(proof_rule),
-                "AnyOf" => parse_any_of(proof_rule),
-                other => Err(anyhow!("Unsupported proof_rule type: {other}")),
-            }
-        }
-        "AllowAll" => Ok(AccessRuleInfo {
-            signers: vec![],
-            threshold: 0,
-            is_updatable: false,
-        }),
-        "DenyAll" => Err(anyhow!("Account has DenyAll access rule")),
-        other => Err(anyhow!("Unsupported rule type: {other}")),
-    }
-}
-
-/// Parse a CountOf proof rule (N-of-M multisig).
-fn parse_count_of(proof_rule: &serde_json::Value) -> Result<AccessRuleInfo> {
-    let count = proof_rule["count"]
-        .as_u64()
-        .ok_or_else(|| anyhow!("Missing 'count' in CountOf rule"))? as u8;
-
-    let list = proof_rule["list"]
-        .as_array()
-        .ok_or_else(|| anyhow!("Missing 'list' in CountOf rule"))?;
+    let rule = match rule_type {
+        "Protected" => parse_access_rule_node(&rule_json["access_rule"])?,
+        "AllowAll" => AccessRuleNode::AllowAll,
+        "DenyAll" => return Err(anyhow!("Account has DenyAll access rule")),
+        other => return Err(anyhow!("Unsupported rule type: {other}")),
+    };
 
-    let signers = list
-        .iter()
-        .map(parse_non_fungible_requirement)
-        .collect::<Result<Vec<_>>>()?;
+    let threshold = rule
+        .min_signatures()
+        .ok_or_else(|| anyhow!("Access rule cannot be satisfied by signatures alone"))?;
 
     Ok(AccessRuleInfo {
-        signers,
-        threshold: count,
+        signers: rule.signers(),
+        threshold: u8::try_from(threshold).unwrap_or(u8::MAX),
         is_updatable: false,
+        rule,
     })
 }
 
-/// Parse a Require proof rule (single signer).
-fn parse_require(proof_rule: &serde_json::Value) -> Result<AccessRuleInfo> {
-    let signer = parse_non_fungible_requirement(&proof_rule["requirement"])?;
+/// Parse an access rule node (ProofRule leaf or AnyOf/AllOf composite).
+fn parse_access_rule_node(node: &serde_json::Value) -> Result<AccessRuleNode> {
+    let node_type = node["type"]
+        .as_str()
+        .ok_or_else(|| anyhow!("Missing 'type' in access_rule"))?;
 
-    Ok(AccessRuleInfo {
-        signers: vec![signer],
-        threshold: 1,
-        is_updatable: false,
-    })
+    match node_type {
+        "ProofRule" => Ok(AccessRuleNode::ProofRule {
+            proof_rule: parse_proof_rule(&node["proof_rule"])?,
+        }),
+        "AnyOf" => Ok(AccessRuleNode::AnyOf {
+            access_rules: parse_access_rule_nodes(node)?,
+        }),
+        "AllOf" => Ok(AccessRuleNode::AllOf {
+            access_rules: parse_access_rule_nodes(node)?,
+        }),
+        other => Err(anyhow!("Unsupported access_rule type: {other}")),
+    }
 }
 
-/// Parse AllOf proof rule (all must sign).
-fn parse_all_of(proof_rule: &serde_json::Value) -> Result<AccessRuleInfo> {
-    let list = proof_rule["list"]
+fn parse_access_rule_nodes(node: &serde_json::Value) -> Result<Vec<AccessRuleNode>> {
+    node["access_rules"]
         .as_array()
-        .ok_or_else(|| anyhow!("Missing 'list' in AllOf rule"))?;
-
-    let signers = list
+        .ok_or_else(|| anyhow!("Missing 'access_rules' in composite access_rule"))?
         .iter()
-        .map(parse_non_fungible_requirement)
-        .collect::<Result<Vec<_>>>()?;
+        .map(parse_access_rule_node)
+        .collect()
+}
 
-    let threshold = signers.len() as u8;
-    Ok(AccessRuleInfo {
-        signers,
-        threshold,
-        is_updatable: false,
-    })
+/// Parse a proof rule leaf (Require, AmountOf, CountOf, AllOf, AnyOf).
+fn parse_proof_rule(proof_rule: &serde_json::Value) -> Result<ProofRule> {
+    let proof_rule_type = proof_rule["type"]
+        .as_str()
+        .ok_or_else(|| anyhow!("Missing 'type' in proof_rule"))?;
+
+    match proof_rule_type {
+        "Require" => Ok(ProofRule::Require {
+            requirement: parse_requirement(&proof_rule["requirement"])?,
+        }),
+        "AmountOf" => Ok(ProofRule::AmountOf {
+            amount: proof_rule["amount"]
+                .as_str()
+                .ok_or_else(|| anyhow!("Missing 'amount' in AmountOf rule"))?
+                .to_string(),
+            resource: proof_rule["resource"]
+                .as_str()
+                .ok_or_else(|| anyhow!("Missing 'resource' in AmountOf rule"))?
+                .to_string(),
+        }),
+        "CountOf" => {
+            let count = proof_rule["count"]
+                .as_u64()
+                .ok_or_else(|| anyhow!("Missing 'count' in CountOf rule"))?;
+            Ok(ProofRule::CountOf {
+                count: u8::try_from(count)
+                    .map_err(|_| anyhow!("CountOf count {count} out of range"))?,
+                list: parse_requirement_list(proof_rule, "CountOf")?,
+            })
+        }
+        "AllOf" => Ok(ProofRule::AllOf {
+            list: parse_requirement_list(proof_rule, "AllOf")?,
+        }),
+        "AnyOf" => Ok(ProofRule::AnyOf {
+            list: parse_requirement_list(proof_rule, "AnyOf")?,
+        }),
+        other => Err(anyhow!("Unsupported proof_rule type: {other}")),
+    }
 }
 
-/// Parse AnyOf proof rule (any one can sign).
-fn parse_any_of(proof_rule: &serde_json::Value) -> Result<AccessRuleInfo> {
-    let list = proof_rule["list"]
+fn parse_requirement_list(proof_rule: &serde_json::Value, kind: &str) -> Result<Vec<Requirement>> {
+    proof_rule["list"]
         .as_array()
-        .ok_or_else(|| anyhow!("Missing 'list' in AnyOf rule"))?;
-
-    let signers = list
+        .ok_or_else(|| anyhow!("Missing 'list' in {kind} rule"))?
         .iter()
-        .map(parse_non_fungible_requirement)
-        .collect::<Result<Vec<_>>>()?;
-
-    Ok(AccessRuleInfo {
-        signers,
-        threshold: 1,
-        is_updatable: false,
-    })
+        .map(parse_requirement)
+        .collect()
 }
 
-/// Parse a NonFungible requirement into SignerInfo.
-fn parse_non_fungible_requirement(req: &serde_json::Value) -> Result<SignerInfo> {
+/// Parse a requirement into a signature badge, non-fungible badge or resource.
+fn parse_requirement(req: &serde_json::Value) -> Result<Requirement> {
     let req_type = req["type"]
         .as_str()
         .ok_or_else(|| anyhow!("Missing 'type' in requirement"))?;
 
-    if req_type != "NonFungible" {
-        return Err(anyhow!("Expected NonFungible requirement, got: {req_type}"));
+    match req_type {
+        "NonFungible" => parse_non_fungible_requirement(req),
+        "Resource" => Ok(Requirement::Resource {
+            resource: req["resource"]
+                .as_str()
+                .ok_or_else(|| anyhow!("Missing resource in Resource requirement"))?
+                .to_string(),
+        }),
+        other => Err(anyhow!("Unsupported requirement type: {other}")),
     }
+}
 
+/// Parse a NonFungible requirement, recognising virtual signature badges as signers.
+fn parse_non_fungible_requirement(req: &serde_json::Value) -> Result<Requirement> {
     let nf = &req["non_fungible"];
 
     let resource_address = nf["resource_address"]
@@ -404,29 +1078,33 @@ fn parse_non_fungible_requirement(req: &serde_json::Value) -> Result<SignerInfo>
         .as_str()
         .ok_or_else(|| anyhow!("Missing simple_rep in local_id"))?;
 
-    // Extract the hex hash from simple_rep, e.g. "[a0c2219f...]" -> "a0c2219f..."
-    let key_hash = simple_rep
-        .trim_start_matches('[')
-        .trim_end_matches(']')
-        .to_string();
-
     // Determine key type from resource address.
     // Ed25519: resource_*_1nfxxxxxxxxxxed25sgxxxxxxxxx...
     // Secp256k1: resource_*_1nfxxxxxxxxxxsecpsgxxxxxxxxx...
+    // Anything else is an ordinary non-fungible badge, not a signature.
     let key_type = if resource_address.contains("ed25sg") {
         "EddsaEd25519"
     } else if resource_address.contains("secpsg") {
         "EcdsaSecp256k1"
     } else {
-        "Unknown"
+        return Ok(Requirement::NonFungible {
+            resource_address: resource_address.to_string(),
+            local_id: simple_rep.to_string(),
+        });
     };
 
-    Ok(SignerInfo {
+    // Extract the hex hash from simple_rep, e.g. "[a0c2219f...]" -> "a0c2219f..."
+    let key_hash = simple_rep
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+
+    Ok(Requirement::Signer(SignerInfo {
         key_hash,
         key_type: key_type.to_string(),
         badge_resource: resource_address.to_string(),
         badge_local_id: simple_rep.to_string(),
-    })
+    }))
 }
 
 // --- Transaction submission/status response types ---
@@ -436,10 +1114,297 @@ struct SubmitTransactionResponse {
     duplicate: bool,
 }
 
+/// Response of `transaction/subintent-status`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SubintentStatus {
+    /// `CommittedSuccess` once a transaction including the subintent has
+    /// committed successfully, else `Unknown`.
+    pub subintent_status: String,
+    /// Intent hash of the transaction that committed the subintent.
+    pub finalized_at_transaction_intent_hash: Option<String>,
+}
+
+impl SubintentStatus {
+    pub fn is_committed(&self) -> bool {
+        self.subintent_status == "CommittedSuccess"
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TransactionStatusResponse {
     pub status: String,
+    pub intent_status: Option<String>,
+    pub error_message: Option<String>,
+    #[serde(default)]
+    pub known_payloads: Vec<KnownPayload>,
+}
+
+impl TransactionStatusResponse {
+    /// The most specific error the Gateway reported: the top-level message,
+    /// else the latest error on any known payload.
+    pub fn error_detail(&self) -> Option<&str> {
+        self.error_message.as_deref().or_else(|| {
+            self.known_payloads
+                .iter()
+                .find_map(|p| p.latest_error_message.as_deref())
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct KnownPayload {
+    pub payload_status: Option<String>,
+    pub latest_error_message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommittedDetailsResponse {
+    transaction: CommittedTransaction,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommittedTransaction {
+    pub receipt: TransactionReceipt,
+    /// Total XRD fee paid, as a decimal string.
+    #[serde(default)]
+    pub fee_paid: Option<String>,
+}
+
+impl CommittedTransaction {
+    pub fn fee_paid(&self) -> Option<Decimal> {
+        self.fee_paid.as_deref().and_then(|fee| fee.parse().ok())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransactionReceipt {
+    pub status: Option<String>,
+    pub error_message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PreviewV2Response {
+    core_api_receipt: Option<serde_json::Value>,
+}
+
+/// Outcome of simulating a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PreviewStatus {
+    Succeeded,
+    Failed,
+    Rejected,
+    /// The preview could not be run, e.g. the Gateway was unreachable.
+    Error,
+}
+
+/// Net change of one resource in one account, as seen in a preview.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BalanceChange {
+    pub account: String,
+    pub resource: String,
+    /// Signed decimal: negative for withdrawals. For non-fungibles, the number of ids.
+    pub amount: String,
+}
+
+/// What a preview predicts for a transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviewOutcome {
+    pub status: PreviewStatus,
     pub error_message: Option<String>,
+    /// Total XRD fee (execution + finalization + storage + royalties + tip).
+    pub fee_estimate: Option<Decimal>,
+    pub balance_changes: Vec<BalanceChange>,
+}
+
+impl PreviewOutcome {
+    /// A preview that could not be run at all.
+    pub fn error(message: String) -> Self {
+        Self {
+            status: PreviewStatus::Error,
+            error_message: Some(message),
+            fee_estimate: None,
+            balance_changes: Vec::new(),
+        }
+    }
+
+    /// Read the outcome from a Core API receipt.
+    ///
+    /// Balance changes come from the `WithdrawEvent`/`DepositEvent` events that
+    /// accounts emit, netted per account and resource.
+    fn from_core_receipt(receipt: &serde_json::Value) -> Result<Self> {
+        let status = match receipt["status"].as_str() {
+            Some("Succeeded") => PreviewStatus::Succeeded,
+            Some("Failed") => PreviewStatus::Failed,
+            Some("Rejected") => PreviewStatus::Rejected,
+            other => return Err(anyhow!("Unexpected preview receipt status: {other:?}")),
+        };
+
+        let fee_summary = &receipt["fee_summary"];
+        let fee_estimate = if fee_summary.is_object() {
+            let mut total = Decimal::ZERO;
+            for field in [
+                "xrd_total_execution_cost",
+                "xrd_total_finalization_cost",
+                "xrd_total_storage_cost",
+                "xrd_total_royalty_cost",
+                "xrd_total_tipping_cost",
+            ] {
+                if let Some(value) = fee_summary[field].as_str() {
+                    total += parse_decimal(value)?;
+                }
+            }
+            Some(total)
+        } else {
+            None
+        };
+
+        let mut net: Vec<(String, String, Decimal)> = Vec::new();
+        for event in receipt["events"].as_array().into_iter().flatten() {
+            let Some(account) = event["type"]["emitter"]["entity"]["entity_address"]
+                .as_str()
+                .filter(|address| address.starts_with("account_"))
+            else {
+                continue;
+            };
+            let withdrawn = match event["type"]["name"].as_str() {
+                Some("WithdrawEvent") => true,
+                Some("DepositEvent") => false,
+                _ => continue,
+            };
+
+            let data = &event["data"]["programmatic_json"];
+            let fields = &data["fields"];
+            let resource = fields[0]["value"]
+                .as_str()
+                .ok_or_else(|| anyhow!("Account event without a resource address"))?;
+            let amount = match data["variant_name"].as_str() {
+                Some("Fungible") => parse_decimal(fields[1]["value"].as_str().unwrap_or("0"))?,
+                Some("NonFungible") => {
+                    Decimal::from(fields[1]["elements"].as_array().map_or(0, Vec::len) as u64)
+                }
+                other => return Err(anyhow!("Unexpected account event variant: {other:?}")),
+            };
+            let delta = if withdrawn { -amount } else { amount };
+
+            match net
+                .iter_mut()
+                .find(|(a, r, _)| a == account && r == resource)
+            {
+                Some((_, _, total)) => *total += delta,
+                None => net.push((account.to_string(), resource.to_string(), delta)),
+            }
+        }
+
+        Ok(Self {
+            status,
+            error_message: receipt["error_message"].as_str().map(str::to_string),
+            fee_estimate,
+            balance_changes: net
+                .into_iter()
+                .filter(|(_, _, amount)| !amount.is_zero())
+                .map(|(account, resource, amount)| BalanceChange {
+                    account,
+                    resource,
+                    amount: amount.to_string(),
+                })
+                .collect(),
+        })
+    }
+}
+
+fn parse_decimal(value: &str) -> Result<Decimal> {
+    value
+        .parse()
+        .map_err(|e| anyhow!("Invalid decimal '{value}': {e:?}"))
+}
+
+/// Result of polling a submitted transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommitOutcome {
+    /// Not final yet: the transaction can still commit until its epoch
+    /// window has passed.
+    Pending,
+    Committed {
+        fee_paid: Option<Decimal>,
+    },
+    Failed {
+        reason: FailureReason,
+        message: String,
+        /// Set for committed failures, which still pay their fee.
+        fee_paid: Option<Decimal>,
+    },
+}
+
+/// Why a submission attempt failed, classified from Gateway responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum FailureReason {
+    /// The subintent was already committed, by this or another transaction.
+    SubintentAlreadyCommitted,
+    /// The transaction's or subintent's epoch window has passed.
+    EpochExpired,
+    /// The collected signatures did not satisfy an access rule.
+    AuthFailure,
+    /// The fee payer could not lock or repay the fee.
+    InsufficientFee,
+    /// A vault did not hold enough of a resource for the manifest.
+    InsufficientBalance,
+    /// Rejected by the engine for another reason.
+    Rejected,
+    /// Committed, but execution failed for another reason.
+    ExecutionFailed,
+    /// Gave up waiting for a commit. Only found on attempts failed before
+    /// pending transactions were polled until their epoch window passed.
+    Timeout,
+    /// The Gateway could not be reached or refused the request.
+    GatewayError,
+    /// The server could not compose or persist the transaction.
+    ServerError,
+}
+
+impl FailureReason {
+    /// Classify an engine or Gateway error message, using `fallback` when no
+    /// known pattern matches.
+    pub fn classify(message: &str, fallback: FailureReason) -> FailureReason {
+        if message.contains("PreviouslyCommitted") {
+            // The main intent always has a fresh discriminator, so this is the subintent
+            FailureReason::SubintentAlreadyCommitted
+        } else if message.contains("FeeReserveError") || message.contains("LoanRepaymentNotCovered")
+        {
+            // Checked before InsufficientBalance, which also appears inside fee reserve errors
+            FailureReason::InsufficientFee
+        } else if message.contains("InsufficientBalance") {
+            FailureReason::InsufficientBalance
+        } else if message.contains("Unauthorized") || message.contains("AssertAccessRuleFailed") {
+            FailureReason::AuthFailure
+        } else if message.contains("EpochNoLongerValid")
+            || (message.contains("Epoch") && message.contains("Expired"))
+        {
+            FailureReason::EpochExpired
+        } else {
+            fallback
+        }
+    }
+
+    /// Whether resubmitting (with a fresh main transaction) could succeed
+    /// without anything else changing first, or after topping up balances.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            FailureReason::EpochExpired
+            | FailureReason::InsufficientFee
+            | FailureReason::InsufficientBalance
+            | FailureReason::Timeout
+            | FailureReason::GatewayError
+            | FailureReason::ServerError => true,
+            FailureReason::SubintentAlreadyCommitted
+            | FailureReason::AuthFailure
+            | FailureReason::Rejected
+            | FailureReason::ExecutionFailed => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -552,6 +1517,245 @@ mod tests {
         assert_eq!(result.signers[0].key_hash, "abcdef1234567890");
     }
 
+    fn signer_json(key_hash: &str) -> serde_json::Value {
+        serde_json::json!({
+            "type": "NonFungible",
+            "non_fungible": {
+                "local_id": { "simple_rep": format!("[{key_hash}]") },
+                "resource_address": "resource_tdx_2_1nfxxxxxxxxxxed25sgxxxxxxxxx002236757237xxxxxxxxx3e2cpa"
+            }
+        })
+    }
+
+    /// "2 of council AND 1 of auditors"
+    fn nested_council_and_auditors_json() -> serde_json::Value {
+        serde_json::json!({
+            "type": "Protected",
+            "access_rule": {
+                "type": "AllOf",
+                "access_rules": [
+                    {
+                        "type": "ProofRule",
+                        "proof_rule": {
+                            "type": "CountOf",
+                            "count": 2,
+                            "list": [signer_json("c1"), signer_json("c2"), signer_json("c3")]
+                        }
+                    },
+                    {
+                        "type": "ProofRule",
+                        "proof_rule": {
+                            "type": "CountOf",
+                            "count": 1,
+                            "list": [signer_json("a1"), signer_json("a2")]
+                        }
+                    }
+                ]
+            }
+        })
+    }
+
+    #[test]
+    fn batched_entity_details_yield_per_account_rules() {
+        let response: EntityDetailsResponse = serde_json::from_value(serde_json::json!({
+            "ledger_state": { "epoch": 100, "state_version": 1234 },
+            "items": [
+                {
+                    "address": "account_tdx_2_multisig",
+                    "details": {
+                        "role_assignments": {
+                            "owner": {
+                                "rule": multisig_role_assignments_json(),
+                                "updater": "Owner"
+                            }
+                        }
+                    }
+                },
+                { "address": "account_tdx_2_plain" }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(response.ledger_state.unwrap().state_version, 1234);
+
+        let mut items = response.items.into_iter();
+        let rule = access_rule_from_item(items.next().unwrap()).unwrap();
+        assert_eq!(rule.threshold, 3);
+        assert!(rule.is_updatable);
+        assert!(access_rule_from_item(items.next().unwrap()).is_err());
+    }
+
+    #[test]
+    fn parse_nested_composite_access_rule() {
+        let result = parse_access_rule(&nested_council_and_auditors_json()).unwrap();
+
+        assert_eq!(result.signers.len(), 5);
+        assert_eq!(result.threshold, 3);
+        assert!(matches!(result.rule, AccessRuleNode::AllOf { .. }));
+    }
+
+    #[test]
+    fn nested_rule_requires_every_branch() {
+        let result = parse_access_rule(&nested_council_and_auditors_json()).unwrap();
+
+        // Three council members satisfy the count but not the auditor branch
+        assert!(!result.is_satisfied_by(["c1", "c2", "c3"]));
+        assert!(!result.is_satisfied_by(["c1", "a1", "a2"]));
+        assert!(result.is_satisfied_by(["c1", "c3", "a2"]));
+    }
+
+    #[test]
+    fn signatures_needed_counts_each_unmet_branch() {
+        let result = parse_access_rule(&nested_council_and_auditors_json()).unwrap();
+
+        assert_eq!(result.signatures_needed([]), Some(3));
+        // A surplus council signature doesn't stand in for the auditor
+        assert_eq!(result.signatures_needed(["c1", "c2", "c3"]), Some(1));
+        assert_eq!(result.signatures_needed(["a1", "a2"]), Some(2));
+        assert_eq!(result.signatures_needed(["c1", "a1"]), Some(1));
+        assert_eq!(result.signatures_needed(["c1", "c3", "a2"]), Some(0));
+    }
+
+    #[test]
+    fn signatures_needed_skips_branches_signatures_cannot_meet() {
+        let json = serde_json::json!({
+            "type": "Protected",
+            "access_rule": {
+                "type": "AnyOf",
+                "access_rules": [
+                    {
+                        "type": "ProofRule",
+                        "proof_rule": {
+                            "type": "AmountOf",
+                            "amount": "1",
+                            "resource": "resource_tdx_2_badge"
+                        }
+                    },
+                    {
+                        "type": "ProofRule",
+                        "proof_rule": {
+                            "type": "CountOf",
+                            "count": 2,
+                            "list": [signer_json("s1"), signer_json("s2")]
+                        }
+                    }
+                ]
+            }
+        });
+
+        let result = parse_access_rule(&json).unwrap();
+        assert_eq!(result.signatures_needed(["s1"]), Some(1));
+        assert_eq!(result.signatures_needed(["s1", "s2"]), Some(0));
+
+        let AccessRuleNode::AnyOf { access_rules } = result.rule else {
+            panic!("expected AnyOf");
+        };
+        let with_badge = AccessRuleNode::AllOf { access_rules };
+        assert_eq!(
+            with_badge.signatures_needed(&HashSet::from(["s1", "s2"])),
+            None
+        );
+    }
+
+    #[test]
+    fn minimal_signers_drops_surplus_signatures() {
+        let result = parse_access_rule(&nested_council_and_auditors_json()).unwrap();
+
+        let selected = result
+            .minimal_signers(&["c1", "c2", "c3", "a1", "a2"])
+            .unwrap();
+        assert_eq!(selected, vec!["c1", "c2", "a1"]);
+        assert_eq!(result.minimal_signers(&["c1", "c2", "c3"]), None);
+    }
+
+    #[test]
+    fn minimal_signers_prefers_cheapest_branch() {
+        let json = serde_json::json!({
+            "type": "Protected",
+            "access_rule": {
+                "type": "AnyOf",
+                "access_rules": [
+                    {
+                        "type": "ProofRule",
+                        "proof_rule": {
+                            "type": "AllOf",
+                            "list": [signer_json("s1"), signer_json("s2"), signer_json("s3")]
+                        }
+                    },
+                    {
+                        "type": "ProofRule",
+                        "proof_rule": {
+                            "type": "Require",
+                            "requirement": signer_json("admin")
+                        }
+                    }
+                ]
+            }
+        });
+
+        let result = parse_access_rule(&json).unwrap();
+        assert_eq!(
+            result.minimal_signers(&["s1", "s2", "s3", "admin"]),
+            Some(vec!["admin"])
+        );
+        assert_eq!(
+            result.minimal_signers(&["s1", "s2", "s3"]),
+            Some(vec!["s1", "s2", "s3"])
+        );
+    }
+
+    #[test]
+    fn resource_requirement_is_not_satisfied_by_signatures() {
+        let json = serde_json::json!({
+            "type": "Protected",
+            "access_rule": {
+                "type": "AnyOf",
+                "access_rules": [
+                    {
+                        "type": "ProofRule",
+                        "proof_rule": {
+                            "type": "Require",
+                            "requirement": {
+                                "type": "Resource",
+                                "resource": "resource_tdx_2_1t4admin"
+                            }
+                        }
+                    },
+                    {
+                        "type": "ProofRule",
+                        "proof_rule": {
+                            "type": "CountOf",
+                            "count": 2,
+                            "list": [signer_json("s1"), signer_json("s2")]
+                        }
+                    }
+                ]
+            }
+        });
+
+        let result = parse_access_rule(&json).unwrap();
+        assert_eq!(result.threshold, 2);
+        assert_eq!(result.signers.len(), 2);
+        assert!(!result.is_satisfied_by(["s1"]));
+        assert!(result.is_satisfied_by(["s1", "s2"]));
+    }
+
+    #[test]
+    fn rejects_rule_unsatisfiable_by_signatures() {
+        let json = serde_json::json!({
+            "type": "Protected",
+            "access_rule": {
+                "type": "ProofRule",
+                "proof_rule": {
+                    "type": "AmountOf",
+                    "amount": "10",
+                    "resource": "resource_tdx_2_1t4admin"
+                }
+            }
+        });
+
+        assert!(parse_access_rule(&json).is_err());
+    }
+
     #[test]
     fn parse_allow_all_access_rule() {
         let json = serde_json::json!({ "type": "AllowAll" });
@@ -567,6 +1771,171 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn classifies_engine_failures() {
+        let cases = [
+            (
+                "IntentHashPreviouslyCommitted(Subintent(SubintentHash(...)))",
+                FailureReason::SubintentAlreadyCommitted,
+            ),
+            (
+                "CostingError(FeeReserveError(InsufficientBalance { required: 1.2, remaining: 0.3 }))",
+                FailureReason::InsufficientFee,
+            ),
+            (
+                "ApplicationError(VaultError(ResourceError(InsufficientBalance { requested: 100, actual: 5 })))",
+                FailureReason::InsufficientBalance,
+            ),
+            (
+                "SystemModuleError(AuthError(Unauthorized(UnauthorizedError { .. })))",
+                FailureReason::AuthFailure,
+            ),
+            (
+                "TransactionEpochNoLongerValid { valid_until: 1100, current_epoch: 1200 }",
+                FailureReason::EpochExpired,
+            ),
+        ];
+        for (message, expected) in cases {
+            assert_eq!(
+                FailureReason::classify(message, FailureReason::Rejected),
+                expected,
+                "{message}"
+            );
+        }
+
+        assert_eq!(
+            FailureReason::classify("SomethingElse", FailureReason::ExecutionFailed),
+            FailureReason::ExecutionFailed
+        );
+    }
+
+    #[test]
+    fn retryable_failure_reasons() {
+        assert!(FailureReason::InsufficientFee.is_retryable());
+        assert!(FailureReason::Timeout.is_retryable());
+        assert!(!FailureReason::SubintentAlreadyCommitted.is_retryable());
+        assert!(!FailureReason::AuthFailure.is_retryable());
+    }
+
+    #[test]
+    fn status_error_detail_falls_back_to_known_payloads() {
+        let status: TransactionStatusResponse = serde_json::from_value(serde_json::json!({
+            "status": "Rejected",
+            "intent_status": "PermanentlyRejected",
+            "error_message": null,
+            "known_payloads": [
+                {
+                    "payload_status": "PermanentlyRejected",
+                    "latest_error_message": "TransactionEpochNoLongerValid"
+                }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(status.error_detail(), Some("TransactionEpochNoLongerValid"));
+    }
+
+    #[test]
+    fn parses_subintent_status() {
+        let committed: SubintentStatus = serde_json::from_value(serde_json::json!({
+            "ledger_state": { "state_version": 1000 },
+            "subintent_status": "CommittedSuccess",
+            "finalized_at_state_version": 990,
+            "finalized_at_transaction_intent_hash": "txid_tdx_2_1abc"
+        }))
+        .unwrap();
+        assert!(committed.is_committed());
+        assert_eq!(
+            committed.finalized_at_transaction_intent_hash.as_deref(),
+            Some("txid_tdx_2_1abc")
+        );
+
+        let unknown: SubintentStatus = serde_json::from_value(serde_json::json!({
+            "ledger_state": { "state_version": 1000 },
+            "subintent_status": "Unknown"
+        }))
+        .unwrap();
+        assert!(!unknown.is_committed());
+    }
+
+    #[test]
+    fn parses_preview_receipt() {
+        let account = "account_tdx_2_1cx3u3xgr9anc9fk54dxzsz6k2n6lnadludkx4mx5re5erl8jt9lpnp";
+        let xrd = "resource_tdx_2_1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxtfd2jc";
+        let account_event = |name: &str, amount: &str| {
+            serde_json::json!({
+                "type": {
+                    "emitter": {
+                        "type": "Method",
+                        "entity": { "entity_type": "GlobalAccount", "is_global": true, "entity_address": account },
+                        "object_module_id": "Main"
+                    },
+                    "name": name
+                },
+                "data": {
+                    "programmatic_json": {
+                        "kind": "Enum",
+                        "variant_id": 0,
+                        "variant_name": "Fungible",
+                        "fields": [
+                            { "kind": "Reference", "value": xrd },
+                            { "kind": "Decimal", "value": amount }
+                        ]
+                    }
+                }
+            })
+        };
+        let receipt = serde_json::json!({
+            "status": "Succeeded",
+            "fee_summary": {
+                "xrd_total_execution_cost": "0.25",
+                "xrd_total_finalization_cost": "0.05",
+                "xrd_total_royalty_cost": "0",
+                "xrd_total_storage_cost": "0.1",
+                "xrd_total_tipping_cost": "0"
+            },
+            "events": [
+                account_event("WithdrawEvent", "100"),
+                account_event("DepositEvent", "40"),
+                { "type": { "emitter": { "type": "Method", "entity": { "entity_address": "internal_vault_tdx_2_1abc" } }, "name": "WithdrawEvent" } }
+            ]
+        });
+
+        let outcome = PreviewOutcome::from_core_receipt(&receipt).unwrap();
+
+        assert_eq!(outcome.status, PreviewStatus::Succeeded);
+        assert_eq!(
+            outcome.fee_estimate,
+            Some("0.4".parse::<Decimal>().unwrap())
+        );
+        assert_eq!(
+            outcome.balance_changes,
+            vec![BalanceChange {
+                account: account.to_string(),
+                resource: xrd.to_string(),
+                amount: "-60".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_failed_preview_receipt() {
+        let receipt = serde_json::json!({
+            "status": "Failed",
+            "error_message": "ApplicationError(VaultError(ResourceError(InsufficientBalance { .. })))"
+        });
+
+        let outcome = PreviewOutcome::from_core_receipt(&receipt).unwrap();
+
+        assert_eq!(outcome.status, PreviewStatus::Failed);
+        assert!(outcome
+            .error_message
+            .unwrap()
+            .contains("InsufficientBalance"));
+        assert!(outcome.fee_estimate.is_none());
+        assert!(outcome.balance_changes.is_empty());
+    }
+
     #[test]
     #[ignore]
     fn integration_read_access_rule_from_stokenet() {
@@ -0,0 +1,212 @@
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Consecutive failures after which an endpoint is only tried after healthy ones.
+const UNHEALTHY_AFTER_FAILURES: u32 = 3;
+
+/// How long an unhealthy endpoint stays at the back of the order before it
+/// gets another chance at the front.
+const UNHEALTHY_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Health of one configured Gateway endpoint.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct GatewayEndpointStatus {
+    pub url: String,
+    pub healthy: bool,
+    /// Failed requests since the last successful one.
+    pub consecutive_failures: u32,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_failure_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Default)]
+struct Health {
+    consecutive_failures: u32,
+    last_failure: Option<Instant>,
+    last_success_at: Option<DateTime<Utc>>,
+    last_failure_at: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+}
+
+impl Health {
+    fn is_healthy(&self) -> bool {
+        self.consecutive_failures < UNHEALTHY_AFTER_FAILURES
+            || self
+                .last_failure
+                .is_some_and(|at| at.elapsed() >= UNHEALTHY_COOLDOWN)
+    }
+}
+
+/// The ordered list of Gateway base URLs and how each has been responding.
+///
+/// Requests go to the first healthy endpoint in configured order; endpoints
+/// that keep failing move to the back until their cooldown passes.
+pub struct EndpointPool {
+    urls: Vec<String>,
+    health: Mutex<Vec<Health>>,
+}
+
+impl EndpointPool {
+    pub fn new(urls: Vec<String>) -> Result<Self> {
+        let urls: Vec<String> = urls
+            .into_iter()
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty())
+            .collect();
+        if urls.is_empty() {
+            return Err(anyhow!("At least one Gateway URL is required"));
+        }
+
+        let health = urls.iter().map(|_| Health::default()).collect();
+        Ok(Self {
+            urls,
+            health: Mutex::new(health),
+        })
+    }
+
+    pub fn url(&self, endpoint: usize) -> &str {
+        &self.urls[endpoint]
+    }
+
+    /// Endpoint indices in the order to try them: healthy endpoints in
+    /// configured order, then unhealthy ones.
+    pub fn order(&self) -> Vec<usize> {
+        let health = self.lock();
+        let (mut healthy, unhealthy): (Vec<usize>, Vec<usize>) =
+            (0..self.urls.len()).partition(|&i| health[i].is_healthy());
+        healthy.extend(unhealthy);
+        healthy
+    }
+
+    pub fn record_success(&self, endpoint: usize) {
+        let mut health = self.lock();
+        let health = &mut health[endpoint];
+        health.consecutive_failures = 0;
+        health.last_success_at = Some(Utc::now());
+    }
+
+    pub fn record_failure(&self, endpoint: usize, error: &str) {
+        let mut health = self.lock();
+        let health = &mut health[endpoint];
+        health.consecutive_failures += 1;
+        health.last_failure = Some(Instant::now());
+        health.last_failure_at = Some(Utc::now());
+        health.last_error = Some(error.to_string());
+    }
+
+    pub fn status(&self) -> Vec<GatewayEndpointStatus> {
+        let health = self.lock();
+        self.urls
+            .iter()
+            .zip(health.iter())
+            .map(|(url, health)| GatewayEndpointStatus {
+                url: url.clone(),
+                healthy: health.is_healthy(),
+                consecutive_failures: health.consecutive_failures,
+                last_success_at: health.last_success_at,
+                last_failure_at: health.last_failure_at,
+                last_error: health.last_error.clone(),
+            })
+            .collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Health>> {
+        self.health.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// How often, and how far apart, failed Gateway requests are retried.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Retries after the first try; each retry goes through the endpoints again.
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `retry` (starting at 0): `base_delay`
+    /// doubled per retry and capped at `max_delay`, with the upper half
+    /// jittered so concurrent callers don't retry in lockstep.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_delay);
+        let half = delay / 2;
+        half + half.mul_f64(rand::thread_rng().gen_range(0.0..=1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failing_endpoint_moves_to_the_back() {
+        let pool = EndpointPool::new(vec![
+            "https://primary.example/".into(),
+            "https://secondary.example".into(),
+        ])
+        .unwrap();
+        assert_eq!(pool.url(0), "https://primary.example");
+        assert_eq!(pool.order(), vec![0, 1]);
+
+        for _ in 0..UNHEALTHY_AFTER_FAILURES {
+            pool.record_failure(0, "connection refused");
+        }
+        assert_eq!(pool.order(), vec![1, 0]);
+        let status = pool.status();
+        assert!(!status[0].healthy);
+        assert_eq!(status[0].last_error.as_deref(), Some("connection refused"));
+
+        pool.record_success(0);
+        assert_eq!(pool.order(), vec![0, 1]);
+    }
+
+    #[test]
+    fn requires_an_endpoint() {
+        assert!(EndpointPool::new(vec![" ".into()]).is_err());
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_with_jitter_and_cap() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(1000),
+        };
+        for (retry, full) in [
+            (0, 100),
+            (1, 200),
+            (2, 400),
+            (3, 800),
+            (4, 1000),
+            (10, 1000),
+        ] {
+            let delay = policy.backoff(retry);
+            let full = Duration::from_millis(full);
+            assert!(
+                delay >= full / 2 && delay <= full,
+                "retry {retry}: {delay:?}"
+            );
+        }
+    }
+}
@@ -1,8 +1,14 @@
+mod balance_monitor;
+mod fee_payer;
 mod gateway;
+mod gateway_endpoints;
 mod manifest_analyzer;
+mod notary_signer;
 mod proposal_store;
 mod signature_collector;
+mod submission_worker;
 mod transaction_builder;
+mod ttl_cache;
 mod validity_monitor;
 
 use std::sync::Arc;
@@ -20,16 +26,26 @@ use utoipa::OpenApi;
 use utoipa::ToSchema;
 use utoipa_swagger_ui::SwaggerUi;
 
-use radix_common::address::AddressBech32Encoder;
+use radix_common::math::Decimal;
 use radix_common::network::NetworkDefinition;
-use radix_common::prelude::{ComponentAddress, Ed25519PrivateKey};
 
-use crate::gateway::{AccessRuleInfo, GatewayClient, SignerInfo};
-use crate::proposal_store::{CreateProposal, Proposal, ProposalStatus, ProposalStore};
+use crate::balance_monitor::{BalanceMonitor, FeePayerReport, FeePayerStatus, LowBalancePolicy};
+use crate::fee_payer::{FeePayer, FeePayerPool};
+use crate::gateway::{
+    AccessRuleInfo, AccessRuleNode, BalanceChange, FailureReason, GatewayClient, PreviewOutcome,
+    PreviewStatus, ProofRule, Requirement, SignerInfo,
+};
+use crate::gateway_endpoints::{GatewayEndpointStatus, RetryPolicy};
+use crate::notary_signer::{EncryptedKeystore, LocalKeySigner, NotarySigner, RemoteSigner};
+use crate::proposal_store::{
+    CreateProposal, FeeMode, Proposal, ProposalStatus, ProposalStore, SubmissionAttempt,
+    ValidityDecision, ValidityDecisionKind,
+};
 use crate::signature_collector::{
-    SignatureCollector, SignatureStatus, SignatureSummary, SignerStatus,
+    SignatureCollector, SignatureOutcome, SignatureOutcomeKind, SignatureStatus, SignatureSummary,
+    SignerStatus,
 };
-use crate::transaction_builder::StoredSignature;
+use crate::submission_worker::{FeeLockPolicy, Submitter};
 
 #[derive(Clone)]
 pub struct AppState {
@@ -37,15 +53,44 @@ pub struct AppState {
     pub signature_collector: Arc<SignatureCollector>,
     pub gateway: Arc<GatewayClient>,
     pub network_id: u8,
-    /// Raw bytes of the server's fee-payer Ed25519 private key.
-    pub fee_payer_key_bytes: [u8; 32],
-    /// Bech32-encoded preallocated account address for the fee payer (for logging/recording).
-    pub fee_payer_account: String,
+    pub submitter: Arc<Submitter>,
+    pub preview_failure_policy: PreviewFailurePolicy,
+    pub balance_monitor: Arc<BalanceMonitor>,
+}
+
+/// What to do with a proposal whose creation-time preview does not succeed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewFailurePolicy {
+    /// Refuse to create the proposal.
+    Reject,
+    /// Create it anyway, with the failed preview recorded on the proposal.
+    /// A preview that could not be run at all is recorded as an error.
+    Flag,
+}
+
+impl std::str::FromStr for PreviewFailurePolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "reject" => Ok(Self::Reject),
+            "flag" => Ok(Self::Flag),
+            other => Err(anyhow::anyhow!(
+                "Unknown preview failure policy '{other}' (expected 'reject' or 'flag')"
+            )),
+        }
+    }
 }
 
 #[derive(serde::Serialize, ToSchema)]
 struct HealthResponse {
+    /// `ok`, or `degraded` when a fee payer is running low on XRD or no
+    /// Gateway endpoint is healthy.
     status: &'static str,
+    /// Latest fee-payer balance check, once one has completed.
+    fee_payers: Option<FeePayerReport>,
+    /// Configured Gateway endpoints, in failover order.
+    gateway_endpoints: Vec<GatewayEndpointStatus>,
 }
 
 #[derive(serde::Serialize, ToSchema)]
@@ -69,8 +114,47 @@ fn err_response(
         (status = 200, description = "Server is healthy", body = HealthResponse)
     )
 )]
-async fn health() -> Json<HealthResponse> {
-    Json(HealthResponse { status: "ok" })
+async fn health(State(state): State<AppState>) -> Json<HealthResponse> {
+    let fee_payers = state.balance_monitor.latest().await;
+    let gateway_endpoints = state.gateway.endpoint_status();
+    let status = if fee_payers.as_ref().is_some_and(FeePayerReport::any_low)
+        || !gateway_endpoints.iter().any(|endpoint| endpoint.healthy)
+    {
+        "degraded"
+    } else {
+        "ok"
+    };
+    Json(HealthResponse {
+        status,
+        fee_payers,
+        gateway_endpoints,
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/fee-payers",
+    tag = "health",
+    responses(
+        (status = 200, description = "XRD balances of the server fee payers", body = FeePayerReport),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+async fn get_fee_payers(
+    State(state): State<AppState>,
+) -> Result<Json<FeePayerReport>, (axum::http::StatusCode, Json<ErrorResponse>)> {
+    if let Some(report) = state.balance_monitor.latest().await {
+        return Ok(Json(report));
+    }
+
+    // No background check has finished yet
+    state.balance_monitor.check().await.map(Json).map_err(|e| {
+        tracing::error!("Failed to check fee payer balances: {e}");
+        err_response(
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to check fee payer balances: {e}"),
+        )
+    })
 }
 
 // --- Proposal endpoints ---
@@ -82,6 +166,18 @@ struct CreateProposalRequest {
     /// When provided, skip manifest analysis and use this address directly.
     /// Useful for SET_OWNER_ROLE manifests that the analyzer can't detect.
     multisig_account: Option<String>,
+    /// Hex public key of the proposer (Ed25519 or compressed Secp256k1).
+    /// When provided, the proposer may cancel the proposal even if they are not a signer.
+    proposer_public_key: Option<String>,
+    /// Submit automatically, with the server fee payer, once enough signatures are collected.
+    #[serde(default)]
+    auto_submit: bool,
+    /// Who pays the transaction fee. With `multisig`, a `lock_fee` on the
+    /// multisig account is added to the start of the manifest signers approve;
+    /// with `reimburse_fee_payer`, a withdraw from the multisig and deposit to
+    /// the fee payer of the previewed fee is added before `YIELD_TO_PARENT`.
+    #[serde(default)]
+    fee_mode: FeeMode,
 }
 
 #[utoipa::path(
@@ -91,7 +187,7 @@ struct CreateProposalRequest {
     request_body = CreateProposalRequest,
     responses(
         (status = 200, description = "Proposal created", body = Proposal),
-        (status = 400, description = "Invalid manifest or no multisig account found", body = ErrorResponse),
+        (status = 400, description = "Invalid manifest, no multisig account found, or preview failed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
@@ -99,6 +195,18 @@ async fn create_proposal(
     State(state): State<AppState>,
     Json(req): Json<CreateProposalRequest>,
 ) -> Result<Json<Proposal>, (axum::http::StatusCode, Json<ErrorResponse>)> {
+    let proposer_key_hash = req
+        .proposer_public_key
+        .as_deref()
+        .map(signature_collector::compute_key_hash)
+        .transpose()
+        .map_err(|e| {
+            err_response(
+                axum::http::StatusCode::BAD_REQUEST,
+                format!("Invalid proposer public key: {e}"),
+            )
+        })?;
+
     // Compile manifest first — used for both analysis and subintent building
     let compiled_manifest =
         transaction_builder::compile_subintent_manifest(&req.manifest_text, state.network_id)
@@ -202,26 +310,128 @@ async fn create_proposal(
         ));
     }
 
+    let build_subintent = |compiled_manifest| {
+        transaction_builder::build_unsigned_subintent_from_compiled(
+            compiled_manifest,
+            state.network_id,
+            epoch_min,
+            epoch_max,
+        )
+        .map_err(|e| {
+            tracing::error!("Failed to build subintent: {e}");
+            err_response(
+                axum::http::StatusCode::BAD_REQUEST,
+                format!("Failed to build subintent: {e}"),
+            )
+        })
+    };
+    let recompile = |manifest_text: &str| {
+        transaction_builder::compile_subintent_manifest(manifest_text, state.network_id).map_err(
+            |e| {
+                tracing::error!("Failed to compile manifest with fee instructions: {e}");
+                err_response(
+                    axum::http::StatusCode::BAD_REQUEST,
+                    format!("Failed to compile manifest with fee instructions: {e}"),
+                )
+            },
+        )
+    };
+
+    // Fee instructions are part of the manifest signers approve
+    let (manifest_text, compiled_manifest, reimbursed_fee_payer, fee_reimbursement) =
+        match req.fee_mode {
+            FeeMode::FeePayer => (req.manifest_text, compiled_manifest, None, None),
+            // Lock up to the cap from the multisig itself; only the fee used is charged
+            FeeMode::Multisig => {
+                let manifest_text = transaction_builder::prepend_lock_fee(
+                    &req.manifest_text,
+                    &multisig_account,
+                    state.submitter.fee_lock.max,
+                );
+                let compiled_manifest = recompile(&manifest_text)?;
+                (manifest_text, compiled_manifest, None, None)
+            }
+            // Pay back the fee payer the amount a preview says it will lock
+            FeeMode::ReimburseFeePayer => {
+                let unreimbursed = build_subintent(compiled_manifest)?;
+                let (fee_payer_account, amount) = state
+                    .submitter
+                    .size_fee_reimbursement(&unreimbursed.partial_transaction_bytes)
+                    .await
+                    .map_err(|e| {
+                        tracing::error!("Failed to size fee reimbursement: {e:#}");
+                        err_response(
+                            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                            format!("Failed to size fee reimbursement: {e:#}"),
+                        )
+                    })?;
+                let manifest_text = transaction_builder::append_fee_reimbursement(
+                    &req.manifest_text,
+                    state.network_id,
+                    &multisig_account,
+                    &fee_payer_account,
+                    amount,
+                )
+                .map_err(|e| {
+                    err_response(
+                        axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                        format!("Failed to add fee reimbursement: {e}"),
+                    )
+                })?;
+                let compiled_manifest = recompile(&manifest_text)?;
+                (
+                    manifest_text,
+                    compiled_manifest,
+                    Some(fee_payer_account),
+                    Some(amount.to_string()),
+                )
+            }
+        };
+
     // Build the unsigned subintent from the already-compiled manifest
-    let subintent_result = transaction_builder::build_unsigned_subintent_from_compiled(
-        compiled_manifest,
-        state.network_id,
-        epoch_min,
-        epoch_max,
-    )
-    .map_err(|e| {
-        tracing::error!("Failed to build subintent: {e}");
-        err_response(
-            axum::http::StatusCode::BAD_REQUEST,
-            format!("Failed to build subintent: {e}"),
+    let subintent_result = build_subintent(compiled_manifest)?;
+
+    // Simulate the subintent so proposals that cannot succeed don't collect signatures
+    let preview = match state
+        .submitter
+        .preview_subintent(
+            &subintent_result.partial_transaction_bytes,
+            req.fee_mode,
+            reimbursed_fee_payer.as_deref(),
         )
-    })?;
+        .await
+    {
+        Ok(preview) => preview,
+        Err(e) => {
+            tracing::error!("Failed to preview proposal: {e:#}");
+            if state.preview_failure_policy == PreviewFailurePolicy::Reject {
+                return Err(err_response(
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Failed to preview proposal: {e:#}"),
+                ));
+            }
+            PreviewOutcome::error(format!("Failed to preview proposal: {e:#}"))
+        }
+    };
+    if preview.status != PreviewStatus::Succeeded {
+        let error = preview
+            .error_message
+            .as_deref()
+            .unwrap_or("no error message");
+        if state.preview_failure_policy == PreviewFailurePolicy::Reject {
+            return Err(err_response(
+                axum::http::StatusCode::BAD_REQUEST,
+                format!("Proposal preview did not succeed: {error}"),
+            ));
+        }
+        tracing::warn!("Creating proposal despite failed preview: {error}");
+    }
 
     // Store the proposal
     let proposal = state
         .proposal_store
         .create(CreateProposal {
-            manifest_text: req.manifest_text,
+            manifest_text,
             multisig_account,
             epoch_min: epoch_min as i64,
             epoch_max: epoch_max as i64,
@@ -230,6 +440,12 @@ async fn create_proposal(
             min_proposer_timestamp: subintent_result.min_proposer_timestamp,
             max_proposer_timestamp: subintent_result.max_proposer_timestamp,
             partial_transaction_bytes: subintent_result.partial_transaction_bytes,
+            proposer_key_hash,
+            auto_submit: req.auto_submit,
+            preview: Some(preview),
+            fee_mode: req.fee_mode,
+            reimbursed_fee_payer,
+            fee_reimbursement,
         })
         .await
         .map_err(|e| {
@@ -302,6 +518,128 @@ async fn get_proposal(
     Ok(Json(proposal))
 }
 
+#[derive(serde::Deserialize, ToSchema)]
+struct CancelProposalRequest {
+    /// Hex public key of the caller (Ed25519 or compressed Secp256k1).
+    public_key_hex: String,
+    /// Hex signature by that key over the Blake2b-256 hash of
+    /// `multisig-cancel:{proposal_id}:{subintent_hash}`.
+    signature_hex: String,
+    reason: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/proposals/{id}/cancel",
+    tag = "proposals",
+    params(
+        ("id" = Uuid, Path, description = "Proposal ID")
+    ),
+    request_body = CancelProposalRequest,
+    responses(
+        (status = 200, description = "Proposal cancelled", body = Proposal),
+        (status = 400, description = "Invalid signature or proposal not in a cancellable status", body = ErrorResponse),
+        (status = 403, description = "Caller is neither the proposer nor a current signer", body = ErrorResponse),
+        (status = 404, description = "Proposal not found", body = ErrorResponse),
+        (status = 409, description = "Proposal was handed over for external submission", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+async fn cancel_proposal(
+    State(state): State<AppState>,
+    Path(id): Path<uuid::Uuid>,
+    Json(req): Json<CancelProposalRequest>,
+) -> Result<Json<Proposal>, (axum::http::StatusCode, Json<ErrorResponse>)> {
+    let proposal = state
+        .proposal_store
+        .get(id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get proposal: {e}");
+            err_response(
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to get proposal: {e}"),
+            )
+        })?
+        .ok_or_else(|| {
+            err_response(
+                axum::http::StatusCode::NOT_FOUND,
+                "Proposal not found".to_string(),
+            )
+        })?;
+
+    if !proposal.status.can_transition_to(ProposalStatus::Cancelled) {
+        return Err(err_response(
+            axum::http::StatusCode::BAD_REQUEST,
+            format!(
+                "Proposal is in {:?} status and can no longer be cancelled",
+                proposal.status
+            ),
+        ));
+    }
+
+    // Its transaction may still commit through whoever submits it
+    if proposal.externally_submitted_at.is_some() {
+        return Err(err_response(
+            axum::http::StatusCode::CONFLICT,
+            "Proposal was handed over for external submission and can no longer be cancelled"
+                .to_string(),
+        ));
+    }
+
+    let subintent_hash = proposal.subintent_hash.as_deref().ok_or_else(|| {
+        err_response(
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            "Proposal has no subintent hash".to_string(),
+        )
+    })?;
+
+    // Prove control of the key before checking whether it is allowed to cancel
+    let message = signature_collector::cancellation_message_hash(id, subintent_hash);
+    let key_hash = signature_collector::verify_message_signature(
+        &message,
+        &req.public_key_hex,
+        &req.signature_hex,
+    )
+    .map_err(|e| err_response(axum::http::StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    let is_proposer = proposal.proposer_key_hash.as_deref() == Some(key_hash.as_str());
+    if !is_proposer {
+        let access_rule = state
+            .gateway
+            .read_access_rule(&proposal.multisig_account)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to read access rule: {e}");
+                err_response(
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Failed to read access rule: {e}"),
+                )
+            })?;
+
+        if signature_collector::find_signer_by_hash(&access_rule, &key_hash).is_none() {
+            return Err(err_response(
+                axum::http::StatusCode::FORBIDDEN,
+                "Only the proposer or a current signer can cancel this proposal".to_string(),
+            ));
+        }
+    }
+
+    let cancelled = state
+        .proposal_store
+        .cancel(id, &key_hash, &req.reason)
+        .await
+        .map_err(|e| {
+            let msg = e.to_string();
+            tracing::warn!("Cancel proposal failed: {msg}");
+            err_response(axum::http::StatusCode::BAD_REQUEST, msg)
+        })?;
+
+    tracing::info!("Proposal {id} cancelled by {key_hash}: {}", req.reason);
+
+    Ok(Json(cancelled))
+}
+
 // --- Signature endpoints ---
 
 #[derive(serde::Deserialize, ToSchema)]
@@ -318,8 +656,8 @@ struct SignProposalRequest {
     ),
     request_body = SignProposalRequest,
     responses(
-        (status = 200, description = "Signature accepted", body = SignatureStatus),
-        (status = 400, description = "Invalid signature or signer not in access rule", body = ErrorResponse),
+        (status = 200, description = "At least one signature accepted; per-signature outcomes included", body = SignatureStatus),
+        (status = 400, description = "Invalid signature, or no signature accepted (duplicates or signers not in access rule)", body = ErrorResponse),
         (status = 404, description = "Proposal not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
@@ -386,6 +724,7 @@ async fn sign_proposal(
             let status = if msg.contains("not found") {
                 axum::http::StatusCode::NOT_FOUND
             } else if msg.contains("not in the current access rule")
+                || msg.contains("Invalid signature")
                 || msg.contains("already signed")
                 || msg.contains("status")
                 || msg.contains("different subintent hash")
@@ -461,11 +800,133 @@ async fn get_signature_status(
 
 // --- Submission endpoints ---
 
+#[derive(serde::Serialize, ToSchema)]
+struct SignedPartialResponse {
+    proposal_id: uuid::Uuid,
+    /// Bech32-encoded subintent hash (e.g. "subtxid_...").
+    subintent_hash: Option<String>,
+    /// Hex-encoded `SignedPartialTransactionV2` with every collected signature.
+    signed_partial_transaction_hex: String,
+    /// When the proposal was marked as externally submitted, if it has been.
+    externally_submitted_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Build the fully-signed partial transaction of a Ready proposal.
+async fn export_signed_partial(
+    state: &AppState,
+    id: uuid::Uuid,
+) -> Result<SignedPartialResponse, (axum::http::StatusCode, Json<ErrorResponse>)> {
+    let proposal = state
+        .proposal_store
+        .get(id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get proposal: {e}");
+            err_response(
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to get proposal: {e}"),
+            )
+        })?
+        .ok_or_else(|| {
+            err_response(
+                axum::http::StatusCode::NOT_FOUND,
+                "Proposal not found".to_string(),
+            )
+        })?;
+
+    if proposal.status != ProposalStatus::Ready {
+        return Err(err_response(
+            axum::http::StatusCode::BAD_REQUEST,
+            format!(
+                "Proposal is in {:?} status; must be Ready to export",
+                proposal.status
+            ),
+        ));
+    }
+
+    let signed_partial = state.submitter.signed_partial(id).await.map_err(|e| {
+        tracing::error!("Failed to build signed partial for proposal {id}: {e:#}");
+        err_response(
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            format!("{e:#}"),
+        )
+    })?;
+    let signed_partial_transaction_hex = transaction_builder::signed_partial_hex(&signed_partial)
+        .map_err(|e| {
+        err_response(
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            format!("{e}"),
+        )
+    })?;
+
+    Ok(SignedPartialResponse {
+        proposal_id: id,
+        subintent_hash: proposal.subintent_hash,
+        signed_partial_transaction_hex,
+        externally_submitted_at: proposal.externally_submitted_at,
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/proposals/{id}/signed-partial",
+    tag = "submissions",
+    params(
+        ("id" = Uuid, Path, description = "Proposal ID")
+    ),
+    responses(
+        (status = 200, description = "Fully-signed partial transaction, for composing into another transaction", body = SignedPartialResponse),
+        (status = 400, description = "Proposal not in Ready status", body = ErrorResponse),
+        (status = 404, description = "Proposal not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+async fn get_signed_partial(
+    State(state): State<AppState>,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<SignedPartialResponse>, (axum::http::StatusCode, Json<ErrorResponse>)> {
+    export_signed_partial(&state, id).await.map(Json)
+}
+
+#[utoipa::path(
+    post,
+    path = "/proposals/{id}/signed-partial/external",
+    tag = "submissions",
+    params(
+        ("id" = Uuid, Path, description = "Proposal ID")
+    ),
+    responses(
+        (status = 200, description = "Proposal handed over for external submission; the server stops submitting it and watches for its subintent to commit", body = SignedPartialResponse),
+        (status = 400, description = "Proposal not in Ready status", body = ErrorResponse),
+        (status = 404, description = "Proposal not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+async fn submit_externally(
+    State(state): State<AppState>,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<SignedPartialResponse>, (axum::http::StatusCode, Json<ErrorResponse>)> {
+    let mut exported = export_signed_partial(&state, id).await?;
+
+    let proposal = state
+        .proposal_store
+        .mark_externally_submitted(id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to mark proposal {id} as externally submitted: {e}");
+            err_response(axum::http::StatusCode::BAD_REQUEST, format!("{e}"))
+        })?;
+    exported.externally_submitted_at = proposal.externally_submitted_at;
+
+    Ok(Json(exported))
+}
+
 #[derive(serde::Serialize, ToSchema)]
 struct SubmitProposalResponse {
+    /// Submission attempt handed to the background worker.
+    attempt_id: uuid::Uuid,
     status: String,
-    tx_id: Option<String>,
-    error: Option<String>,
+    tx_id: String,
 }
 
 #[utoipa::path(
@@ -476,8 +937,8 @@ struct SubmitProposalResponse {
         ("id" = Uuid, Path, description = "Proposal ID")
     ),
     responses(
-        (status = 200, description = "Submission result (may include tx_id even on failure)", body = SubmitProposalResponse),
-        (status = 400, description = "Proposal not in Ready status", body = ErrorResponse),
+        (status = 202, description = "Submission accepted; poll the proposal for the outcome", body = SubmitProposalResponse),
+        (status = 400, description = "Proposal not in Ready status, or exported for external submission", body = ErrorResponse),
         (status = 404, description = "Proposal not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
@@ -485,7 +946,10 @@ struct SubmitProposalResponse {
 async fn submit_proposal(
     State(state): State<AppState>,
     Path(id): Path<uuid::Uuid>,
-) -> Result<Json<SubmitProposalResponse>, (axum::http::StatusCode, Json<ErrorResponse>)> {
+) -> Result<
+    (axum::http::StatusCode, Json<SubmitProposalResponse>),
+    (axum::http::StatusCode, Json<ErrorResponse>),
+> {
     // Validate proposal is in Ready state
     let proposal = state
         .proposal_store
@@ -514,64 +978,99 @@ async fn submit_proposal(
             ),
         ));
     }
+    if proposal.externally_submitted_at.is_some() {
+        return Err(err_response(
+            axum::http::StatusCode::BAD_REQUEST,
+            "Proposal was exported for external submission".to_string(),
+        ));
+    }
 
-    // Reconstruct the fee payer private key from stored bytes
-    let fee_payer_private_key =
-        Ed25519PrivateKey::from_bytes(&state.fee_payer_key_bytes).map_err(|e| {
-            tracing::error!("Failed to reconstruct fee payer key: {e:?}");
-            err_response(
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                "Server fee payer key misconfigured".to_string(),
-            )
-        })?;
-    let fee_payer_account =
-        ComponentAddress::preallocated_account_from_public_key(&fee_payer_private_key.public_key());
+    let started = state.submitter.submit(id).await.map_err(|e| {
+        tracing::error!("Failed to submit proposal {id}: {e:#}");
+        err_response(
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            format!("{e:#}"),
+        )
+    })?;
 
-    // Reconstruct the DAO withdrawal signed partial from stored data
-    let partial_bytes = state
+    Ok((
+        axum::http::StatusCode::ACCEPTED,
+        Json(SubmitProposalResponse {
+            attempt_id: started.attempt_id,
+            status: "pending".to_string(),
+            tx_id: started.tx_hash,
+        }),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/proposals/{id}/retry",
+    tag = "submissions",
+    params(
+        ("id" = Uuid, Path, description = "Proposal ID")
+    ),
+    responses(
+        (status = 202, description = "Proposal reopened and resubmitted with a fresh main transaction", body = SubmitProposalResponse),
+        (status = 400, description = "Proposal not Failed, or its epoch window has passed", body = ErrorResponse),
+        (status = 404, description = "Proposal not found", body = ErrorResponse),
+        (status = 409, description = "A submission attempt has already committed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+async fn retry_proposal(
+    State(state): State<AppState>,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<
+    (axum::http::StatusCode, Json<SubmitProposalResponse>),
+    (axum::http::StatusCode, Json<ErrorResponse>),
+> {
+    let proposal = state
         .proposal_store
-        .get_partial_transaction_bytes(id)
+        .get(id)
         .await
         .map_err(|e| {
-            tracing::error!("Failed to get partial transaction bytes: {e}");
+            tracing::error!("Failed to get proposal: {e}");
             err_response(
                 axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Failed to get partial transaction bytes: {e}"),
+                format!("Failed to get proposal: {e}"),
+            )
+        })?
+        .ok_or_else(|| {
+            err_response(
+                axum::http::StatusCode::NOT_FOUND,
+                "Proposal not found".to_string(),
             )
         })?;
 
-    let raw_sigs = state
-        .signature_collector
-        .get_raw_signatures(id)
+    if proposal.status != ProposalStatus::Failed {
+        return Err(err_response(
+            axum::http::StatusCode::BAD_REQUEST,
+            format!(
+                "Proposal is in {:?} status; only Failed proposals can be retried",
+                proposal.status
+            ),
+        ));
+    }
+
+    let committed = state
+        .proposal_store
+        .has_committed_attempt(id)
         .await
         .map_err(|e| {
-            tracing::error!("Failed to get signatures: {e}");
+            tracing::error!("Failed to check submission attempts: {e}");
             err_response(
                 axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Failed to get signatures: {e}"),
+                format!("Failed to check submission attempts: {e}"),
             )
         })?;
+    if committed {
+        return Err(err_response(
+            axum::http::StatusCode::CONFLICT,
+            "A submission attempt for this proposal has already committed".to_string(),
+        ));
+    }
 
-    let stored_sigs: Vec<StoredSignature> = raw_sigs
-        .into_iter()
-        .map(|(pk, sig)| StoredSignature {
-            public_key_hex: pk,
-            signature_bytes: sig,
-        })
-        .collect();
-
-    let withdrawal_signed_partial =
-        transaction_builder::reconstruct_signed_partial(&partial_bytes, &stored_sigs).map_err(
-            |e| {
-                tracing::error!("Failed to reconstruct signed partial: {e}");
-                err_response(
-                    axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                    format!("Failed to reconstruct signed partial: {e}"),
-                )
-            },
-        )?;
-
-    // Get current epoch for the main transaction
     let current_epoch = state.gateway.get_current_epoch().await.map_err(|e| {
         tracing::error!("Failed to get current epoch: {e}");
         err_response(
@@ -579,120 +1078,142 @@ async fn submit_proposal(
             format!("Failed to get current epoch: {e}"),
         )
     })?;
+    if current_epoch >= proposal.epoch_max as u64 {
+        return Err(err_response(
+            axum::http::StatusCode::BAD_REQUEST,
+            format!(
+                "Proposal epoch window has passed (epoch {current_epoch} >= epoch_max {})",
+                proposal.epoch_max
+            ),
+        ));
+    }
 
-    // Compose the main transaction (server pays fee via its own account)
-    let composed = transaction_builder::compose_main_transaction(
-        state.network_id,
-        current_epoch,
-        &fee_payer_private_key,
-        fee_payer_account,
-        withdrawal_signed_partial,
-    )
-    .map_err(|e| {
-        tracing::error!("Failed to compose main transaction: {e}");
+    // Failed → Ready, then resubmit; a fresh main transaction gets a new discriminator
+    state
+        .proposal_store
+        .reopen_failed(id, current_epoch)
+        .await
+        .map_err(|e| err_response(axum::http::StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    let started = state.submitter.submit(id).await.map_err(|e| {
+        tracing::error!("Failed to resubmit proposal {id}: {e:#}");
         err_response(
             axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Failed to compose main transaction: {e}"),
+            format!("Proposal is Ready again but resubmission failed: {e:#}"),
         )
     })?;
 
-    // Transition Ready → Submitting
+    Ok((
+        axum::http::StatusCode::ACCEPTED,
+        Json(SubmitProposalResponse {
+            attempt_id: started.attempt_id,
+            status: "pending".to_string(),
+            tx_id: started.tx_hash,
+        }),
+    ))
+}
+
+/// A submission attempt, with whether retrying after its failure is meaningful.
+#[derive(serde::Serialize, ToSchema)]
+struct SubmissionAttemptResponse {
+    id: uuid::Uuid,
+    fee_payer_account: Option<String>,
+    tx_hash: Option<String>,
+    /// `pending`, `submitted`, `committed` or `failed`.
+    status: String,
+    failure_reason: Option<FailureReason>,
+    error_message: Option<String>,
+    /// True when the failure is one a `POST /proposals/{id}/retry` could overcome.
+    retryable: bool,
+    /// XRD fee estimated by previewing the transaction.
+    fee_estimate: Option<String>,
+    /// XRD locked for fees.
+    fee_locked: Option<String>,
+    /// XRD fee actually paid, once committed.
+    fee_paid: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<SubmissionAttempt> for SubmissionAttemptResponse {
+    fn from(attempt: SubmissionAttempt) -> Self {
+        Self {
+            id: attempt.id,
+            fee_payer_account: attempt.fee_payer_account,
+            tx_hash: attempt.tx_hash,
+            status: attempt.status,
+            retryable: attempt
+                .failure_reason
+                .is_some_and(|reason| reason.is_retryable()),
+            failure_reason: attempt.failure_reason,
+            error_message: attempt.error_message,
+            fee_estimate: attempt.fee_estimate,
+            fee_locked: attempt.fee_locked,
+            fee_paid: attempt.fee_paid,
+            created_at: attempt.created_at,
+            updated_at: attempt.updated_at,
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/proposals/{id}/submissions",
+    tag = "submissions",
+    params(
+        ("id" = Uuid, Path, description = "Proposal ID")
+    ),
+    responses(
+        (status = 200, description = "Submission attempts, oldest first", body = Vec<SubmissionAttemptResponse>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+async fn list_submission_attempts(
+    State(state): State<AppState>,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<Vec<SubmissionAttemptResponse>>, (axum::http::StatusCode, Json<ErrorResponse>)> {
     state
         .proposal_store
-        .transition_status(id, ProposalStatus::Ready, ProposalStatus::Submitting)
+        .list_submission_attempts(id)
         .await
+        .map(|attempts| Json(attempts.into_iter().map(Into::into).collect()))
         .map_err(|e| {
-            tracing::error!("Failed to transition to Submitting: {e}");
+            tracing::error!("Failed to list submission attempts: {e}");
             err_response(
                 axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Failed to transition to Submitting: {e}"),
+                format!("Failed to list submission attempts: {e}"),
             )
-        })?;
+        })
+}
 
-    // Record submission attempt
-    let _ = state
+#[utoipa::path(
+    get,
+    path = "/proposals/{id}/validity-decisions",
+    tag = "proposals",
+    params(
+        ("id" = Uuid, Path, description = "Proposal ID")
+    ),
+    responses(
+        (status = 200, description = "Validity monitor decisions about the proposal, oldest first", body = Vec<ValidityDecision>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+async fn list_validity_decisions(
+    State(state): State<AppState>,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<Vec<ValidityDecision>>, (axum::http::StatusCode, Json<ErrorResponse>)> {
+    state
         .proposal_store
-        .record_submission_attempt(
-            id,
-            &state.fee_payer_account,
-            Some(&composed.intent_hash),
-            "submitting",
-            None,
-        )
-        .await;
-
-    // Submit to Gateway
-    let submit_result = state
-        .gateway
-        .submit_transaction(&composed.notarized_transaction_hex)
-        .await;
-
-    match submit_result {
-        Ok(duplicate) => {
-            if duplicate {
-                tracing::warn!("Transaction was a duplicate submission");
-            }
-            tracing::info!("Transaction submitted: {}", composed.intent_hash);
-
-            // Store the tx_id
-            let _ = state
-                .proposal_store
-                .update_tx_id(id, &composed.intent_hash)
-                .await;
-
-            // Poll for commit (max 60 attempts = ~2 minutes)
-            match state
-                .gateway
-                .wait_for_commit(&composed.intent_hash, 60)
-                .await
-            {
-                Ok(_status) => {
-                    let _ = state
-                        .proposal_store
-                        .transition_status(
-                            id,
-                            ProposalStatus::Submitting,
-                            ProposalStatus::Committed,
-                        )
-                        .await;
-
-                    Ok(Json(SubmitProposalResponse {
-                        status: "committed".to_string(),
-                        tx_id: Some(composed.intent_hash),
-                        error: None,
-                    }))
-                }
-                Err(e) => {
-                    let err_msg = e.to_string();
-                    let _ = state
-                        .proposal_store
-                        .transition_status(id, ProposalStatus::Submitting, ProposalStatus::Failed)
-                        .await;
-
-                    Ok(Json(SubmitProposalResponse {
-                        status: "failed".to_string(),
-                        tx_id: Some(composed.intent_hash),
-                        error: Some(err_msg),
-                    }))
-                }
-            }
-        }
-        Err(e) => {
-            let err_msg = e.to_string();
-            tracing::error!("Submit failed: {err_msg}");
-
-            let _ = state
-                .proposal_store
-                .transition_status(id, ProposalStatus::Submitting, ProposalStatus::Failed)
-                .await;
-
-            Ok(Json(SubmitProposalResponse {
-                status: "failed".to_string(),
-                tx_id: Some(composed.intent_hash),
-                error: Some(err_msg),
-            }))
-        }
-    }
+        .list_validity_decisions(id)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            tracing::error!("Failed to list validity decisions: {e}");
+            err_response(
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to list validity decisions: {e}"),
+            )
+        })
 }
 
 // --- Access rule endpoint ---
@@ -728,30 +1249,171 @@ async fn get_access_rule(
     Ok(Json(access_rule))
 }
 
+/// Encrypt a hex Ed25519 key read from stdin into a keystore file, using the
+/// same unlock secret the server will read at startup.
+fn encrypt_keystore(path: &std::path::Path) -> anyhow::Result<()> {
+    let unlock_secret = keystore_unlock_secret()?;
+
+    let mut key_hex = String::new();
+    std::io::stdin().read_line(&mut key_hex)?;
+    let key_bytes = hex::decode(key_hex.trim())
+        .map_err(|e| anyhow::anyhow!("Key on stdin is not valid hex: {e}"))?;
+
+    let keystore = EncryptedKeystore::encrypt(&key_bytes, &unlock_secret)?;
+    std::fs::write(path, serde_json::to_string_pretty(&keystore)?)?;
+    tracing::info!(
+        "Wrote keystore for public key {} to {}",
+        keystore.public_key_hex,
+        path.display()
+    );
+    Ok(())
+}
+
+/// The secret that unlocks fee payer and notary keystores:
+/// `FEE_PAYER_KEYSTORE_PASSWORD`, or the contents of `FEE_PAYER_KEYSTORE_KEY_FILE`.
+fn keystore_unlock_secret() -> anyhow::Result<Vec<u8>> {
+    match (
+        std::env::var("FEE_PAYER_KEYSTORE_PASSWORD"),
+        std::env::var("FEE_PAYER_KEYSTORE_KEY_FILE"),
+    ) {
+        (Ok(password), _) => Ok(password.into_bytes()),
+        (Err(_), Ok(key_file)) => std::fs::read(&key_file)
+            .map_err(|e| anyhow::anyhow!("Failed to read keystore key file {key_file}: {e}")),
+        _ => anyhow::bail!(
+            "Keystores require FEE_PAYER_KEYSTORE_PASSWORD or FEE_PAYER_KEYSTORE_KEY_FILE"
+        ),
+    }
+}
+
+/// Load the notary/fee-payer signers from every configured source:
+/// - `FEE_PAYER_KEYSTORES`: comma-separated encrypted keystore files, unlocked
+///   with `FEE_PAYER_KEYSTORE_PASSWORD` or the contents of `FEE_PAYER_KEYSTORE_KEY_FILE`
+/// - `FEE_PAYER_REMOTE_SIGNERS`: comma-separated remote signer URLs, with an
+///   optional `FEE_PAYER_REMOTE_SIGNER_TOKEN` bearer token, called with
+///   `remote_signer_timeout`
+/// - `FEE_PAYER_PRIVATE_KEYS_HEX` (comma-separated) or `FEE_PAYER_PRIVATE_KEY_HEX`:
+///   raw keys, for development
+async fn load_fee_payer_signers(
+    remote_signer_timeout: std::time::Duration,
+) -> anyhow::Result<Vec<Arc<dyn NotarySigner>>> {
+    fn list(var: &str) -> Vec<String> {
+        std::env::var(var)
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    let mut signers: Vec<Arc<dyn NotarySigner>> = Vec::new();
+
+    let keystores = list("FEE_PAYER_KEYSTORES");
+    if !keystores.is_empty() {
+        let unlock_secret = keystore_unlock_secret()?;
+        for path in keystores {
+            let signer = EncryptedKeystore::open(std::path::Path::new(&path), &unlock_secret)?;
+            signers.push(Arc::new(signer));
+        }
+    }
+
+    let remote_token = std::env::var("FEE_PAYER_REMOTE_SIGNER_TOKEN").ok();
+    for url in list("FEE_PAYER_REMOTE_SIGNERS") {
+        let signer =
+            RemoteSigner::connect(url, remote_token.clone(), remote_signer_timeout).await?;
+        signers.push(Arc::new(signer));
+    }
+
+    let mut raw_keys = list("FEE_PAYER_PRIVATE_KEYS_HEX");
+    if raw_keys.is_empty() {
+        raw_keys = list("FEE_PAYER_PRIVATE_KEY_HEX");
+    }
+    for key_hex in raw_keys {
+        let signer = LocalKeySigner::from_hex(&key_hex)
+            .map_err(|e| anyhow::anyhow!("Invalid fee payer private key: {e}"))?;
+        signers.push(Arc::new(signer));
+    }
+
+    if signers.is_empty() {
+        anyhow::bail!(
+            "No fee payer configured: set FEE_PAYER_KEYSTORES, FEE_PAYER_REMOTE_SIGNERS or FEE_PAYER_PRIVATE_KEY_HEX"
+        );
+    }
+    Ok(signers)
+}
+
+/// Load the dedicated notary key, if one is configured: `NOTARY_KEYSTORE`,
+/// `NOTARY_REMOTE_SIGNER` or `NOTARY_PRIVATE_KEY_HEX`.
+async fn load_notary_signer(
+    remote_signer_timeout: std::time::Duration,
+) -> anyhow::Result<Option<Arc<dyn NotarySigner>>> {
+    if let Ok(path) = std::env::var("NOTARY_KEYSTORE") {
+        let unlock_secret = keystore_unlock_secret()?;
+        let signer = EncryptedKeystore::open(std::path::Path::new(&path), &unlock_secret)?;
+        return Ok(Some(Arc::new(signer)));
+    }
+    if let Ok(url) = std::env::var("NOTARY_REMOTE_SIGNER") {
+        let token = std::env::var("NOTARY_REMOTE_SIGNER_TOKEN").ok();
+        return Ok(Some(Arc::new(
+            RemoteSigner::connect(url, token, remote_signer_timeout).await?,
+        )));
+    }
+    if let Ok(key_hex) = std::env::var("NOTARY_PRIVATE_KEY_HEX") {
+        let signer = LocalKeySigner::from_hex(&key_hex)
+            .map_err(|e| anyhow::anyhow!("Invalid notary private key: {e}"))?;
+        return Ok(Some(Arc::new(signer)));
+    }
+    Ok(None)
+}
+
 #[derive(OpenApi)]
 #[openapi(
     paths(
         health,
+        get_fee_payers,
         create_proposal,
         list_proposals,
         get_proposal,
+        cancel_proposal,
         sign_proposal,
         get_signature_status,
         submit_proposal,
+        get_signed_partial,
+        submit_externally,
+        retry_proposal,
+        list_submission_attempts,
+        list_validity_decisions,
         get_access_rule,
     ),
     components(schemas(
         HealthResponse,
+        FeePayerReport,
+        FeePayerStatus,
+        GatewayEndpointStatus,
         ErrorResponse,
         CreateProposalRequest,
+        CancelProposalRequest,
         SignProposalRequest,
         SubmitProposalResponse,
+        SignedPartialResponse,
+        SubmissionAttemptResponse,
+        FailureReason,
+        PreviewStatus,
+        BalanceChange,
         Proposal,
         ProposalStatus,
+        FeeMode,
+        ValidityDecision,
+        ValidityDecisionKind,
         SignatureStatus,
         SignatureSummary,
+        SignatureOutcome,
+        SignatureOutcomeKind,
         SignerStatus,
         AccessRuleInfo,
+        AccessRuleNode,
+        ProofRule,
+        Requirement,
         SignerInfo,
     ))
 )]
@@ -765,10 +1427,48 @@ async fn main() -> anyhow::Result<()> {
         .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()))
         .init();
 
+    // `multisig-server encrypt-keystore <path>`: read a hex key from stdin and
+    // write it to an encrypted keystore, then exit
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("encrypt-keystore") {
+        let path = args
+            .get(2)
+            .ok_or_else(|| anyhow::anyhow!("Usage: multisig-server encrypt-keystore <path>"))?;
+        return encrypt_keystore(std::path::Path::new(path));
+    }
+
     let port = std::env::var("PORT").unwrap_or_else(|_| "3001".into());
     let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-    let gateway_url = std::env::var("GATEWAY_URL")
-        .unwrap_or_else(|_| "https://babylon-stokenet-gateway.radixdlt.com".into());
+    // Comma-separated, in failover order
+    let gateway_urls: Vec<String> = std::env::var("GATEWAY_URL")
+        .unwrap_or_else(|_| "https://babylon-stokenet-gateway.radixdlt.com".into())
+        .split(',')
+        .map(String::from)
+        .collect();
+    let gateway_connect_timeout_secs: u64 = std::env::var("GATEWAY_CONNECT_TIMEOUT_SECS")
+        .unwrap_or_else(|_| "5".into())
+        .parse()
+        .expect("GATEWAY_CONNECT_TIMEOUT_SECS must be a valid u64");
+    let gateway_request_timeout_secs: u64 = std::env::var("GATEWAY_REQUEST_TIMEOUT_SECS")
+        .unwrap_or_else(|_| "30".into())
+        .parse()
+        .expect("GATEWAY_REQUEST_TIMEOUT_SECS must be a valid u64");
+    let remote_signer_timeout_secs: u64 = std::env::var("REMOTE_SIGNER_TIMEOUT_SECS")
+        .unwrap_or_else(|_| "10".into())
+        .parse()
+        .expect("REMOTE_SIGNER_TIMEOUT_SECS must be a valid u64");
+    let gateway_max_retries: u32 = std::env::var("GATEWAY_MAX_RETRIES")
+        .unwrap_or_else(|_| "3".into())
+        .parse()
+        .expect("GATEWAY_MAX_RETRIES must be a valid u32");
+    let gateway_retry_base_delay_ms: u64 = std::env::var("GATEWAY_RETRY_BASE_DELAY_MS")
+        .unwrap_or_else(|_| "200".into())
+        .parse()
+        .expect("GATEWAY_RETRY_BASE_DELAY_MS must be a valid u64");
+    let gateway_cache_ttl_secs: u64 = std::env::var("GATEWAY_CACHE_TTL_SECS")
+        .unwrap_or_else(|_| "10".into())
+        .parse()
+        .expect("GATEWAY_CACHE_TTL_SECS must be a valid u64");
     let frontend_origin =
         std::env::var("FRONTEND_ORIGIN").unwrap_or_else(|_| "http://localhost:3000".into());
     let network_id: u8 = std::env::var("NETWORK_ID")
@@ -779,31 +1479,63 @@ async fn main() -> anyhow::Result<()> {
         .unwrap_or_else(|_| "30".into())
         .parse()
         .expect("MONITOR_INTERVAL_SECS must be a valid u64");
+    let preview_failure_policy: PreviewFailurePolicy = std::env::var("PREVIEW_FAILURE_POLICY")
+        .unwrap_or_else(|_| "reject".into())
+        .parse()
+        .expect("PREVIEW_FAILURE_POLICY must be 'reject' or 'flag'");
+    let fee_lock_margin_percent: u32 = std::env::var("FEE_LOCK_MARGIN_PERCENT")
+        .unwrap_or_else(|_| "20".into())
+        .parse()
+        .expect("FEE_LOCK_MARGIN_PERCENT must be a valid u32");
+    let fee_lock_max: Decimal = std::env::var("FEE_LOCK_MAX_XRD")
+        .unwrap_or_else(|_| "50".into())
+        .parse()
+        .expect("FEE_LOCK_MAX_XRD must be a valid decimal");
+    let submission_recovery_interval_secs: u64 = std::env::var("SUBMISSION_RECOVERY_INTERVAL_SECS")
+        .unwrap_or_else(|_| "60".into())
+        .parse()
+        .expect("SUBMISSION_RECOVERY_INTERVAL_SECS must be a valid u64");
+    let balance_monitor_interval_secs: u64 = std::env::var("BALANCE_MONITOR_INTERVAL_SECS")
+        .unwrap_or_else(|_| "60".into())
+        .parse()
+        .expect("BALANCE_MONITOR_INTERVAL_SECS must be a valid u64");
+    let fee_payer_min_balance: Decimal = std::env::var("FEE_PAYER_MIN_BALANCE_XRD")
+        .unwrap_or_else(|_| "100".into())
+        .parse()
+        .expect("FEE_PAYER_MIN_BALANCE_XRD must be a valid decimal");
+    let fee_payer_min_submissions: u32 = std::env::var("FEE_PAYER_MIN_SUBMISSIONS")
+        .unwrap_or_else(|_| "20".into())
+        .parse()
+        .expect("FEE_PAYER_MIN_SUBMISSIONS must be a valid u32");
+    let fee_payer_alert_webhook_url = std::env::var("FEE_PAYER_ALERT_WEBHOOK_URL").ok();
 
-    // Fee payer key: server pays tx fees so the wallet doesn't need a fee subintent.
-    let fee_payer_key_hex = std::env::var("FEE_PAYER_PRIVATE_KEY_HEX")
-        .expect("FEE_PAYER_PRIVATE_KEY_HEX must be set (64 hex chars = 32-byte Ed25519 key)");
-    let fee_payer_key_bytes_vec =
-        hex::decode(&fee_payer_key_hex).expect("FEE_PAYER_PRIVATE_KEY_HEX must be valid hex");
-    let fee_payer_key_bytes: [u8; 32] = fee_payer_key_bytes_vec
-        .try_into()
-        .expect("FEE_PAYER_PRIVATE_KEY_HEX must be exactly 32 bytes (64 hex chars)");
-    let fee_payer_private_key =
-        Ed25519PrivateKey::from_bytes(&fee_payer_key_bytes).expect("Invalid fee payer private key");
-    let fee_payer_account_addr =
-        ComponentAddress::preallocated_account_from_public_key(&fee_payer_private_key.public_key());
-    let network_def = match network_id {
-        0x01 => NetworkDefinition::mainnet(),
-        _ => NetworkDefinition::stokenet(),
-    };
-    let addr_encoder = AddressBech32Encoder::new(&network_def);
-    let fee_payer_account = addr_encoder
-        .encode(fee_payer_account_addr.as_bytes())
-        .expect("Failed to encode fee payer address");
-    tracing::info!(
-        "Server fee payer account: {} — fund this with XRD on Stokenet",
-        fee_payer_account
-    );
+    let remote_signer_timeout = std::time::Duration::from_secs(remote_signer_timeout_secs);
+
+    // Fee payer keys: server pays tx fees so the wallet doesn't need a fee subintent.
+    let fee_payers: Vec<FeePayer> = load_fee_payer_signers(remote_signer_timeout)
+        .await?
+        .into_iter()
+        .map(|signer| FeePayer::new(signer, network_id))
+        .collect::<anyhow::Result<_>>()?;
+    for fee_payer in &fee_payers {
+        tracing::info!(
+            "Server fee payer account: {} — fund this with XRD on Stokenet",
+            fee_payer.account
+        );
+    }
+
+    // Optional dedicated notary: fee payers then only sign to authorise lock_fee
+    let notary = load_notary_signer(remote_signer_timeout).await?;
+    if let Some(notary) = &notary {
+        let notary_key = notary.public_key();
+        if fee_payers
+            .iter()
+            .any(|fee_payer| fee_payer.signer.public_key() == notary_key)
+        {
+            anyhow::bail!("The notary key must differ from every fee payer key");
+        }
+        tracing::info!("Using a dedicated notary key; fee payers sign to authorise fees");
+    }
 
     let pool = PgPoolOptions::new()
         .max_connections(5)
@@ -813,27 +1545,90 @@ async fn main() -> anyhow::Result<()> {
     sqlx::migrate!("./migrations").run(&pool).await?;
     tracing::info!("Database migrations applied");
 
-    let gateway = Arc::new(GatewayClient::new(gateway_url));
+    let gateway = Arc::new(
+        GatewayClient::with_endpoints(gateway_urls)?
+            .with_timeouts(
+                std::time::Duration::from_secs(gateway_connect_timeout_secs),
+                std::time::Duration::from_secs(gateway_request_timeout_secs),
+            )?
+            .with_retry_policy(RetryPolicy {
+                max_retries: gateway_max_retries,
+                base_delay: std::time::Duration::from_millis(gateway_retry_base_delay_ms),
+                ..RetryPolicy::default()
+            })
+            .with_cache_ttl(std::time::Duration::from_secs(gateway_cache_ttl_secs)),
+    );
     let proposal_store = Arc::new(ProposalStore::new(pool.clone()));
-    let signature_collector = Arc::new(SignatureCollector::new(pool));
+    let fee_payers = Arc::new(FeePayerPool::new(
+        fee_payers,
+        network_id,
+        gateway.clone(),
+        proposal_store.clone(),
+    )?);
+    let (submission_queue, submission_jobs) = submission_worker::queue();
+    let signature_collector = Arc::new(SignatureCollector::new(pool, submission_queue.clone()));
+    let submitter = Arc::new(Submitter {
+        proposal_store: proposal_store.clone(),
+        signature_collector: signature_collector.clone(),
+        gateway: gateway.clone(),
+        network_id,
+        fee_payers: fee_payers.clone(),
+        notary,
+        fee_lock: FeeLockPolicy {
+            margin: Decimal::from(fee_lock_margin_percent) / Decimal::from(100u32),
+            max: fee_lock_max,
+        },
+        queue: submission_queue,
+    });
+
+    let balance_monitor = Arc::new(BalanceMonitor::new(
+        fee_payers.clone(),
+        proposal_store.clone(),
+        LowBalancePolicy {
+            min_balance: fee_payer_min_balance,
+            min_submissions: fee_payer_min_submissions,
+            // Until submissions record real fees, assume a simple transfer's cost
+            default_fee_estimate: Decimal::ONE,
+            webhook_url: fee_payer_alert_webhook_url,
+        },
+    )?);
+
+    // Start the submission worker, resuming work interrupted by a restart
+    submission_worker::start(submitter.clone(), submission_jobs).await?;
+    tracing::info!("Submission worker started");
+
+    tokio::spawn(submission_worker::run_recovery(
+        submitter.clone(),
+        submission_recovery_interval_secs,
+    ));
+    tracing::info!("Submission recovery started (interval: {submission_recovery_interval_secs}s)");
 
     let state = AppState {
         proposal_store,
         signature_collector,
         gateway,
         network_id,
-        fee_payer_key_bytes,
-        fee_payer_account,
+        submitter,
+        preview_failure_policy,
+        balance_monitor,
     };
 
     // Spawn validity monitor background task
     tokio::spawn(validity_monitor::run(
         state.proposal_store.clone(),
         state.gateway.clone(),
+        state.submitter.queue.clone(),
         monitor_interval_secs,
     ));
     tracing::info!("Validity monitor started (interval: {monitor_interval_secs}s)");
 
+    // Spawn fee payer balance monitor background task
+    tokio::spawn(balance_monitor::run(
+        state.balance_monitor.clone(),
+        balance_monitor_interval_secs,
+    ));
+    tracing::info!("Balance monitor started (interval: {balance_monitor_interval_secs}s)");
+
     let cors = CorsLayer::new()
         .allow_origin(
             frontend_origin
@@ -845,11 +1640,24 @@ async fn main() -> anyhow::Result<()> {
 
     let app = Router::new()
         .route("/health", get(health))
+        .route("/fee-payers", get(get_fee_payers))
         .route("/proposals", post(create_proposal).get(list_proposals))
         .route("/proposals/{id}", get(get_proposal))
+        .route("/proposals/{id}/cancel", post(cancel_proposal))
         .route("/proposals/{id}/sign", post(sign_proposal))
         .route("/proposals/{id}/signatures", get(get_signature_status))
         .route("/proposals/{id}/submit", post(submit_proposal))
+        .route("/proposals/{id}/signed-partial", get(get_signed_partial))
+        .route(
+            "/proposals/{id}/signed-partial/external",
+            post(submit_externally),
+        )
+        .route("/proposals/{id}/retry", post(retry_proposal))
+        .route("/proposals/{id}/submissions", get(list_submission_attempts))
+        .route(
+            "/proposals/{id}/validity-decisions",
+            get(list_validity_decisions),
+        )
         .route("/accounts/{address}/access-rule", get(get_access_rule))
         .merge(SwaggerUi::new("/swagger-ui").url("/api-doc/openapi.json", ApiDoc::openapi()))
         .layer(cors)
@@ -30,20 +30,22 @@ pub fn extract_accounts_requiring_auth(
     let mut accounts = HashSet::new();
 
     for effect in manifest.iter_instruction_effects() {
-        if let ManifestInstructionEffect::Invocation { kind, .. } = effect {
-            if let InvocationKind::Method {
-                address, method, ..
-            } = kind
-            {
-                if let ManifestGlobalAddress::Static(global_addr) = address {
-                    let node_id = global_addr.as_node_id();
-                    if node_id.is_global_account() && AUTH_REQUIRING_METHODS.contains(&method) {
-                        let bech32 = encoder
-                            .encode(&node_id.0)
-                            .map_err(|e| anyhow::anyhow!("Failed to encode address: {e:?}"))?;
-                        accounts.insert(bech32);
-                    }
-                }
+        if let ManifestInstructionEffect::Invocation {
+            kind:
+                InvocationKind::Method {
+                    address: ManifestGlobalAddress::Static(global_addr),
+                    method,
+                    ..
+                },
+            ..
+        } = effect
+        {
+            let node_id = global_addr.as_node_id();
+            if node_id.is_global_account() && AUTH_REQUIRING_METHODS.contains(&method) {
+                let bech32 = encoder
+                    .encode(&node_id.0)
+                    .map_err(|e| anyhow::anyhow!("Failed to encode address: {e:?}"))?;
+                accounts.insert(bech32);
             }
         }
     }
@@ -0,0 +1,439 @@
+//! Backends holding the server keys that notarize the main transaction and
+//! authorise its fee payment.
+//!
+//! - [`LocalKeySigner`]: an in-memory Ed25519 key, from a raw hex value or
+//!   unlocked from an [`EncryptedKeystore`] file.
+//! - [`RemoteSigner`]: a signing service reached over HTTP, so the key never
+//!   enters this process. It speaks a two-call JSON protocol:
+//!   - `GET {url}/public-key` → `{"public_key_hex": "..."}`
+//!   - `POST {url}/sign` with `{"hash_hex": "..."}` → `{"signature_hex": "..."}`
+//!
+//!   Any service implementing those calls (an HSM/KMS bridge, or a local
+//!   stand-in during development) can be plugged in.
+
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use argon2::Argon2;
+use async_trait::async_trait;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use radix_common::prelude::*;
+use radix_transactions::model::SignatureV1;
+use radix_transactions::validation::verify;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::signature_collector::parse_public_key_hex;
+
+/// Signs the main transaction, as its notary or as the fee payer.
+#[async_trait]
+pub trait NotarySigner: Send + Sync {
+    fn public_key(&self) -> PublicKey;
+
+    /// Sign a signed-transaction-intent hash (as notary) or a transaction
+    /// intent hash (as fee payer).
+    async fn sign(&self, hash: &Hash) -> Result<SignatureV1>;
+}
+
+/// Notary key held in memory.
+pub struct LocalKeySigner {
+    private_key: Ed25519PrivateKey,
+}
+
+impl LocalKeySigner {
+    pub fn from_bytes(key_bytes: &[u8]) -> Result<Self> {
+        let private_key = Ed25519PrivateKey::from_bytes(key_bytes)
+            .map_err(|e| anyhow!("Invalid Ed25519 private key: {e:?}"))?;
+        Ok(Self { private_key })
+    }
+
+    /// Load from a 64-char hex Ed25519 private key.
+    pub fn from_hex(key_hex: &str) -> Result<Self> {
+        let key_bytes =
+            hex::decode(key_hex.trim()).map_err(|e| anyhow!("Key is not valid hex: {e}"))?;
+        if key_bytes.len() != Ed25519PrivateKey::LENGTH {
+            return Err(anyhow!(
+                "Key must be exactly {} bytes ({} hex chars)",
+                Ed25519PrivateKey::LENGTH,
+                Ed25519PrivateKey::LENGTH * 2
+            ));
+        }
+        Self::from_bytes(&key_bytes)
+    }
+}
+
+#[async_trait]
+impl NotarySigner for LocalKeySigner {
+    fn public_key(&self) -> PublicKey {
+        self.private_key.public_key().into()
+    }
+
+    async fn sign(&self, hash: &Hash) -> Result<SignatureV1> {
+        Ok(self.private_key.sign(hash).into())
+    }
+}
+
+/// An Ed25519 private key encrypted at rest.
+///
+/// The encryption key is derived with Argon2id (default parameters) from an
+/// unlock secret: a password, or the contents of a key file. The private key
+/// is sealed with ChaCha20-Poly1305.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EncryptedKeystore {
+    pub version: u32,
+    pub kdf: String,
+    pub cipher: String,
+    /// Lets the key be identified (and checked after decryption) without unlocking it.
+    pub public_key_hex: String,
+    pub salt_hex: String,
+    pub nonce_hex: String,
+    pub ciphertext_hex: String,
+}
+
+impl EncryptedKeystore {
+    const VERSION: u32 = 1;
+    const KDF: &'static str = "argon2id";
+    const CIPHER: &'static str = "chacha20poly1305";
+
+    pub fn encrypt(key_bytes: &[u8], unlock_secret: &[u8]) -> Result<Self> {
+        let signer = LocalKeySigner::from_bytes(key_bytes)?;
+
+        let mut salt = [0u8; 16];
+        let mut nonce = [0u8; 12];
+        rand::rngs::OsRng.fill_bytes(&mut salt);
+        rand::rngs::OsRng.fill_bytes(&mut nonce);
+
+        let cipher = ChaCha20Poly1305::new(&derive_key(unlock_secret, &salt)?);
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), key_bytes)
+            .map_err(|_| anyhow!("Failed to encrypt key"))?;
+
+        Ok(Self {
+            version: Self::VERSION,
+            kdf: Self::KDF.to_string(),
+            cipher: Self::CIPHER.to_string(),
+            public_key_hex: public_key_hex(&signer.public_key()),
+            salt_hex: hex::encode(salt),
+            nonce_hex: hex::encode(nonce),
+            ciphertext_hex: hex::encode(ciphertext),
+        })
+    }
+
+    pub fn decrypt(&self, unlock_secret: &[u8]) -> Result<LocalKeySigner> {
+        if self.version != Self::VERSION || self.kdf != Self::KDF || self.cipher != Self::CIPHER {
+            return Err(anyhow!(
+                "Unsupported keystore (version {}, {}, {})",
+                self.version,
+                self.kdf,
+                self.cipher
+            ));
+        }
+
+        let salt = hex::decode(&self.salt_hex).context("Invalid keystore salt")?;
+        let nonce = hex::decode(&self.nonce_hex).context("Invalid keystore nonce")?;
+        let ciphertext =
+            hex::decode(&self.ciphertext_hex).context("Invalid keystore ciphertext")?;
+        if nonce.len() != 12 {
+            return Err(anyhow!("Invalid keystore nonce length"));
+        }
+
+        let cipher = ChaCha20Poly1305::new(&derive_key(unlock_secret, &salt)?);
+        let key_bytes = cipher
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| anyhow!("Failed to unlock keystore: wrong password or key file"))?;
+
+        let signer = LocalKeySigner::from_bytes(&key_bytes)?;
+        if public_key_hex(&signer.public_key()) != self.public_key_hex {
+            return Err(anyhow!(
+                "Keystore public key does not match the decrypted key"
+            ));
+        }
+        Ok(signer)
+    }
+
+    /// Read a keystore file and unlock it.
+    pub fn open(path: &Path, unlock_secret: &[u8]) -> Result<LocalKeySigner> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read keystore {}", path.display()))?;
+        let keystore: Self = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse keystore {}", path.display()))?;
+        keystore
+            .decrypt(unlock_secret)
+            .with_context(|| format!("Failed to unlock keystore {}", path.display()))
+    }
+}
+
+fn public_key_hex(public_key: &PublicKey) -> String {
+    match public_key {
+        PublicKey::Ed25519(key) => hex::encode(key.0),
+        PublicKey::Secp256k1(key) => hex::encode(key.0),
+    }
+}
+
+fn derive_key(unlock_secret: &[u8], salt: &[u8]) -> Result<Key> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(unlock_secret, salt, &mut key)
+        .map_err(|e| anyhow!("Failed to derive keystore key: {e}"))?;
+    Ok(key)
+}
+
+#[derive(Deserialize)]
+struct RemotePublicKeyResponse {
+    public_key_hex: String,
+}
+
+#[derive(Serialize)]
+struct RemoteSignRequest {
+    hash_hex: String,
+}
+
+#[derive(Deserialize)]
+struct RemoteSignResponse {
+    signature_hex: String,
+}
+
+/// Notary key held by a remote signing service (see the module docs for the protocol).
+pub struct RemoteSigner {
+    client: reqwest::Client,
+    base_url: String,
+    bearer_token: Option<String>,
+    public_key: PublicKey,
+}
+
+impl RemoteSigner {
+    /// Fetch the signer's public key; fails if the service is unreachable.
+    /// Every call to the service, this one included, gives up after `timeout`.
+    pub async fn connect(
+        base_url: String,
+        bearer_token: Option<String>,
+        timeout: Duration,
+    ) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(timeout)
+            .timeout(timeout)
+            .build()
+            .context("Failed to build remote signer HTTP client")?;
+        let base_url = base_url.trim_end_matches('/').to_string();
+
+        let mut request = client.get(format!("{base_url}/public-key"));
+        if let Some(token) = &bearer_token {
+            request = request.bearer_auth(token);
+        }
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach remote signer {base_url}"))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Remote signer returned {status}: {error_text}"));
+        }
+        let body: RemotePublicKeyResponse = response
+            .json()
+            .await
+            .context("Failed to parse remote signer public key")?;
+
+        Ok(Self {
+            client,
+            base_url,
+            bearer_token,
+            public_key: parse_public_key_hex(&body.public_key_hex)?,
+        })
+    }
+}
+
+#[async_trait]
+impl NotarySigner for RemoteSigner {
+    fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    async fn sign(&self, hash: &Hash) -> Result<SignatureV1> {
+        let mut request =
+            self.client
+                .post(format!("{}/sign", self.base_url))
+                .json(&RemoteSignRequest {
+                    hash_hex: hex::encode(hash.as_bytes()),
+                });
+        if let Some(token) = &self.bearer_token {
+            request = request.bearer_auth(token);
+        }
+        let response = request
+            .send()
+            .await
+            .context("Failed to reach remote signer")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Remote signer returned {status}: {error_text}"));
+        }
+        let body: RemoteSignResponse = response
+            .json()
+            .await
+            .context("Failed to parse remote signer response")?;
+
+        let signature_bytes =
+            hex::decode(&body.signature_hex).context("Remote signature is not valid hex")?;
+        let signature: SignatureV1 = match self.public_key {
+            PublicKey::Ed25519(_) => Ed25519Signature::try_from(signature_bytes.as_slice())
+                .map_err(|e| anyhow!("Invalid Ed25519 signature from remote signer: {e:?}"))?
+                .into(),
+            PublicKey::Secp256k1(_) => Secp256k1Signature::try_from(signature_bytes.as_slice())
+                .map_err(|e| anyhow!("Invalid Secp256k1 signature from remote signer: {e:?}"))?
+                .into(),
+        };
+
+        // Never notarize with a signature the network would reject
+        if !verify(hash, &self.public_key, &signature) {
+            return Err(anyhow!(
+                "Remote signer returned a signature that does not verify"
+            ));
+        }
+        Ok(signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{routing::get, routing::post, Json, Router};
+
+    use super::*;
+
+    const KEY_HEX: &str = "1111111111111111111111111111111111111111111111111111111111111111";
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    #[tokio::test]
+    async fn local_signer_signature_verifies() {
+        let signer = LocalKeySigner::from_hex(KEY_HEX).unwrap();
+        let message = hash("notarize me");
+
+        let signature = signer.sign(&message).await.unwrap();
+
+        assert!(verify(&message, &signer.public_key(), &signature));
+    }
+
+    #[test]
+    fn keystore_round_trips() {
+        let key_bytes = hex::decode(KEY_HEX).unwrap();
+        let keystore = EncryptedKeystore::encrypt(&key_bytes, b"correct horse").unwrap();
+
+        let signer = keystore.decrypt(b"correct horse").unwrap();
+
+        assert_eq!(
+            signer.public_key(),
+            LocalKeySigner::from_hex(KEY_HEX).unwrap().public_key()
+        );
+        assert!(!keystore.ciphertext_hex.contains(KEY_HEX));
+    }
+
+    #[test]
+    fn keystore_rejects_wrong_secret() {
+        let key_bytes = hex::decode(KEY_HEX).unwrap();
+        let keystore = EncryptedKeystore::encrypt(&key_bytes, b"correct horse").unwrap();
+
+        assert!(keystore.decrypt(b"battery staple").is_err());
+    }
+
+    /// A local stand-in for a remote signing service.
+    async fn spawn_stand_in(signer: Arc<LocalKeySigner>, tamper: bool) -> String {
+        let public_key_hex = public_key_hex(&signer.public_key());
+        let app = Router::new()
+            .route(
+                "/public-key",
+                get(move || async move {
+                    Json(serde_json::json!({ "public_key_hex": public_key_hex }))
+                }),
+            )
+            .route(
+                "/sign",
+                post(move |Json(body): Json<serde_json::Value>| async move {
+                    let hash_bytes = hex::decode(body["hash_hex"].as_str().unwrap()).unwrap();
+                    let mut message = Hash::try_from(hash_bytes.as_slice()).unwrap();
+                    if tamper {
+                        message = hash("something else");
+                    }
+                    let SignatureV1::Ed25519(signature) = signer.sign(&message).await.unwrap()
+                    else {
+                        unreachable!()
+                    };
+                    Json(serde_json::json!({ "signature_hex": hex::encode(signature.0) }))
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{address}")
+    }
+
+    #[tokio::test]
+    async fn remote_signer_signs_through_stand_in() {
+        let local = Arc::new(LocalKeySigner::from_hex(KEY_HEX).unwrap());
+        let url = spawn_stand_in(local.clone(), false).await;
+
+        let remote = RemoteSigner::connect(url, None, TIMEOUT).await.unwrap();
+        let message = hash("notarize me");
+        let signature = remote.sign(&message).await.unwrap();
+
+        assert_eq!(remote.public_key(), local.public_key());
+        assert!(verify(&message, &remote.public_key(), &signature));
+    }
+
+    #[tokio::test]
+    async fn remote_signer_rejects_bad_signature() {
+        let local = Arc::new(LocalKeySigner::from_hex(KEY_HEX).unwrap());
+        let url = spawn_stand_in(local, true).await;
+
+        let remote = RemoteSigner::connect(url, None, TIMEOUT).await.unwrap();
+
+        assert!(remote.sign(&hash("notarize me")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn remote_signer_gives_up_on_unresponsive_service() {
+        // Accepts connections but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                connections.push(stream);
+            }
+        });
+
+        let started = std::time::Instant::now();
+        let result = RemoteSigner::connect(url, None, Duration::from_millis(200)).await;
+
+        assert!(result.is_err());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn remote_signer_gives_up_on_hanging_sign() {
+        let local = LocalKeySigner::from_hex(KEY_HEX).unwrap();
+        let public_key_hex = public_key_hex(&local.public_key());
+        let app = Router::new()
+            .route(
+                "/public-key",
+                get(move || async move {
+                    Json(serde_json::json!({ "public_key_hex": public_key_hex }))
+                }),
+            )
+            .route("/sign", post(std::future::pending::<()>));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let remote = RemoteSigner::connect(url, None, Duration::from_millis(200))
+            .await
+            .unwrap();
+        let started = std::time::Instant::now();
+
+        assert!(remote.sign(&hash("notarize me")).await.is_err());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
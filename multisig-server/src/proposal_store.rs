@@ -1,10 +1,15 @@
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
+use radix_common::math::Decimal;
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, PgPool};
+use sqlx::types::Json;
+use sqlx::{FromRow, PgConnection, PgPool};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::gateway::{BalanceChange, FailureReason, PreviewOutcome, PreviewStatus};
+use crate::transaction_builder::ComposedTransaction;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
 #[sqlx(type_name = "proposal_status", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
@@ -17,6 +22,7 @@ pub enum ProposalStatus {
     Failed,
     Expired,
     Invalid,
+    Cancelled,
 }
 
 impl ProposalStatus {
@@ -26,19 +32,45 @@ impl ProposalStatus {
             (self, to),
             (ProposalStatus::Created, ProposalStatus::Signing)
                 | (ProposalStatus::Signing, ProposalStatus::Ready)
+                | (ProposalStatus::Ready, ProposalStatus::Signing)
                 | (ProposalStatus::Ready, ProposalStatus::Submitting)
+                | (ProposalStatus::Signing, ProposalStatus::Committed)
+                | (ProposalStatus::Ready, ProposalStatus::Committed)
                 | (ProposalStatus::Submitting, ProposalStatus::Committed)
                 | (ProposalStatus::Submitting, ProposalStatus::Failed)
+                | (ProposalStatus::Failed, ProposalStatus::Committed)
                 | (ProposalStatus::Created, ProposalStatus::Expired)
                 | (ProposalStatus::Signing, ProposalStatus::Expired)
                 | (ProposalStatus::Ready, ProposalStatus::Expired)
                 | (ProposalStatus::Created, ProposalStatus::Invalid)
                 | (ProposalStatus::Signing, ProposalStatus::Invalid)
                 | (ProposalStatus::Ready, ProposalStatus::Invalid)
+                | (ProposalStatus::Created, ProposalStatus::Cancelled)
+                | (ProposalStatus::Signing, ProposalStatus::Cancelled)
+                | (ProposalStatus::Ready, ProposalStatus::Cancelled)
+                | (ProposalStatus::Failed, ProposalStatus::Ready)
         )
     }
 }
 
+/// Who pays a proposal's transaction fee.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema,
+)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum FeeMode {
+    /// The server's fee payer locks the fee in the main intent.
+    #[default]
+    FeePayer,
+    /// The multisig account locks the fee inside the subintent, so the main
+    /// intent carries no fee lock of its own.
+    Multisig,
+    /// The server's fee payer locks the fee, and the subintent pays it back
+    /// with a withdraw from the multisig and a deposit to that fee payer.
+    ReimburseFeePayer,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct Proposal {
     pub id: Uuid,
@@ -55,6 +87,95 @@ pub struct Proposal {
     pub submitted_at: Option<DateTime<Utc>>,
     pub tx_id: Option<String>,
     pub invalid_reason: Option<String>,
+    pub proposer_key_hash: Option<String>,
+    pub cancelled_by: Option<String>,
+    pub cancel_reason: Option<String>,
+    pub cancelled_at: Option<DateTime<Utc>>,
+    pub auto_submit: bool,
+    pub preview_status: Option<PreviewStatus>,
+    pub preview_error: Option<String>,
+    /// Estimated XRD fee from the creation-time preview.
+    pub preview_fee_estimate: Option<String>,
+    #[schema(value_type = Option<Vec<BalanceChange>>)]
+    pub preview_balance_changes: Option<Json<Vec<BalanceChange>>>,
+    pub fee_mode: FeeMode,
+    /// Fee payer account the subintent reimburses; it must pay the fee.
+    pub reimbursed_fee_payer: Option<String>,
+    /// XRD the subintent pays back to `reimbursed_fee_payer`.
+    pub fee_reimbursement: Option<String>,
+    /// When the signed partial was exported for a third party to submit.
+    /// The server no longer submits the proposal, only watches for its commit.
+    pub externally_submitted_at: Option<DateTime<Utc>>,
+}
+
+/// Columns selected into a [`Proposal`] row.
+const PROPOSAL_COLUMNS: &str = "id, manifest_text, multisig_account, epoch_min, epoch_max, \
+     status, subintent_hash, intent_discriminator, min_proposer_timestamp, max_proposer_timestamp, \
+     created_at, submitted_at, tx_id, invalid_reason, \
+     proposer_key_hash, cancelled_by, cancel_reason, cancelled_at, auto_submit, \
+     preview_status, preview_error, preview_fee_estimate, preview_balance_changes, fee_mode, \
+     reimbursed_fee_payer, fee_reimbursement, externally_submitted_at";
+
+/// One try at submitting a proposal's transaction, driven by the submission worker.
+///
+/// `status` moves from `pending` (persisted, not yet sent) to `submitted`
+/// (accepted by the Gateway) and then to `committed` or `failed`.
+#[derive(Debug, Clone, FromRow)]
+pub struct SubmissionAttempt {
+    pub id: Uuid,
+    pub proposal_id: Uuid,
+    /// Account of the fee-payer key used; unset if the attempt failed before one was chosen.
+    pub fee_payer_account: Option<String>,
+    pub tx_hash: Option<String>,
+    pub status: String,
+    pub error_message: Option<String>,
+    pub failure_reason: Option<FailureReason>,
+    pub notarized_transaction_hex: Option<String>,
+    /// XRD fee estimated by previewing the composed transaction.
+    pub fee_estimate: Option<String>,
+    /// XRD locked for fees in the main intent.
+    pub fee_locked: Option<String>,
+    /// XRD fee actually paid, from the commit receipt.
+    pub fee_paid: Option<String>,
+    /// First epoch in which the transaction can no longer commit; unset for
+    /// attempts persisted before it was recorded.
+    pub end_epoch_exclusive: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Columns selected into a [`SubmissionAttempt`] row.
+const SUBMISSION_ATTEMPT_COLUMNS: &str = "id, proposal_id, fee_payer_account, tx_hash, status, \
+     error_message, failure_reason, notarized_transaction_hex, fee_estimate, fee_locked, fee_paid, \
+     end_epoch_exclusive, created_at, updated_at";
+
+/// What the validity monitor decided about a proposal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ValidityDecisionKind {
+    /// A signer left the access rule; their signature no longer counts.
+    SignatureInvalidated,
+    /// A signer was added back to the access rule; their signature counts again.
+    SignatureRevalidated,
+    /// Valid signatures meet the current access rule (Signing→Ready).
+    Ready,
+    /// Valid signatures no longer meet the current access rule (Ready→Signing).
+    BackToSigning,
+    /// Signer removals left the proposal unable to reach its threshold.
+    Invalidated,
+}
+
+/// One recorded validity monitor decision, with the reason for it.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ValidityDecision {
+    pub id: Uuid,
+    pub proposal_id: Uuid,
+    pub decision: ValidityDecisionKind,
+    /// The signer concerned, for signature decisions.
+    pub signer_key_hash: Option<String>,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
 }
 
 pub struct CreateProposal {
@@ -67,6 +188,21 @@ pub struct CreateProposal {
     pub min_proposer_timestamp: i64,
     pub max_proposer_timestamp: i64,
     pub partial_transaction_bytes: Vec<u8>,
+    pub proposer_key_hash: Option<String>,
+    pub auto_submit: bool,
+    pub preview: Option<PreviewOutcome>,
+    pub fee_mode: FeeMode,
+    pub reimbursed_fee_payer: Option<String>,
+    pub fee_reimbursement: Option<String>,
+}
+
+/// XRD a submission locks from a fee payer, checked against what the payer's
+/// other in-flight submissions have already locked when it is persisted.
+pub struct FeeReservation<'a> {
+    pub account: &'a str,
+    pub amount: Decimal,
+    /// The payer's XRD balance on ledger.
+    pub balance: Decimal,
 }
 
 pub struct ProposalStore {
@@ -79,15 +215,14 @@ impl ProposalStore {
     }
 
     pub async fn create(&self, input: CreateProposal) -> Result<Proposal> {
-        let row = sqlx::query_as::<_, Proposal>(
+        let preview = input.preview.as_ref();
+        let row = sqlx::query_as::<_, Proposal>(&format!(
             r#"
-            INSERT INTO proposals (manifest_text, multisig_account, epoch_min, epoch_max, subintent_hash, intent_discriminator, min_proposer_timestamp, max_proposer_timestamp, partial_transaction_bytes)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
-            RETURNING id, manifest_text, multisig_account, epoch_min, epoch_max,
-                      status, subintent_hash, intent_discriminator, min_proposer_timestamp, max_proposer_timestamp,
-                      created_at, submitted_at, tx_id, invalid_reason
+            INSERT INTO proposals (manifest_text, multisig_account, epoch_min, epoch_max, subintent_hash, intent_discriminator, min_proposer_timestamp, max_proposer_timestamp, partial_transaction_bytes, proposer_key_hash, auto_submit, preview_status, preview_error, preview_fee_estimate, preview_balance_changes, fee_mode, reimbursed_fee_payer, fee_reimbursement)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
+            RETURNING {PROPOSAL_COLUMNS}
             "#,
-        )
+        ))
         .bind(&input.manifest_text)
         .bind(&input.multisig_account)
         .bind(input.epoch_min)
@@ -97,6 +232,15 @@ impl ProposalStore {
         .bind(input.min_proposer_timestamp)
         .bind(input.max_proposer_timestamp)
         .bind(&input.partial_transaction_bytes)
+        .bind(&input.proposer_key_hash)
+        .bind(input.auto_submit)
+        .bind(preview.map(|p| p.status))
+        .bind(preview.and_then(|p| p.error_message.as_deref()))
+        .bind(preview.and_then(|p| p.fee_estimate).map(|fee| fee.to_string()))
+        .bind(preview.map(|p| Json(&p.balance_changes)))
+        .bind(input.fee_mode)
+        .bind(&input.reimbursed_fee_payer)
+        .bind(&input.fee_reimbursement)
         .fetch_one(&self.pool)
         .await?;
 
@@ -104,15 +248,13 @@ impl ProposalStore {
     }
 
     pub async fn get(&self, id: Uuid) -> Result<Option<Proposal>> {
-        let row = sqlx::query_as::<_, Proposal>(
+        let row = sqlx::query_as::<_, Proposal>(&format!(
             r#"
-            SELECT id, manifest_text, multisig_account, epoch_min, epoch_max,
-                   status, subintent_hash, intent_discriminator, min_proposer_timestamp, max_proposer_timestamp,
-                   created_at, submitted_at, tx_id, invalid_reason
+            SELECT {PROPOSAL_COLUMNS}
             FROM proposals
             WHERE id = $1
             "#,
-        )
+        ))
         .bind(id)
         .fetch_optional(&self.pool)
         .await?;
@@ -120,16 +262,36 @@ impl ProposalStore {
         Ok(row)
     }
 
+    /// Read a proposal and lock its row until the surrounding transaction
+    /// ends, so concurrent writers to the same proposal are serialised.
+    pub async fn get_for_update(
+        &self,
+        conn: &mut PgConnection,
+        id: Uuid,
+    ) -> Result<Option<Proposal>> {
+        let row = sqlx::query_as::<_, Proposal>(&format!(
+            r#"
+            SELECT {PROPOSAL_COLUMNS}
+            FROM proposals
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+        ))
+        .bind(id)
+        .fetch_optional(conn)
+        .await?;
+
+        Ok(row)
+    }
+
     pub async fn list(&self) -> Result<Vec<Proposal>> {
-        let rows = sqlx::query_as::<_, Proposal>(
+        let rows = sqlx::query_as::<_, Proposal>(&format!(
             r#"
-            SELECT id, manifest_text, multisig_account, epoch_min, epoch_max,
-                   status, subintent_hash, intent_discriminator, min_proposer_timestamp, max_proposer_timestamp,
-                   created_at, submitted_at, tx_id, invalid_reason
+            SELECT {PROPOSAL_COLUMNS}
             FROM proposals
             ORDER BY created_at DESC
             "#,
-        )
+        ))
         .fetch_all(&self.pool)
         .await?;
 
@@ -164,50 +326,400 @@ impl ProposalStore {
         Ok(())
     }
 
-    /// Record a submission attempt for audit trail.
-    pub async fn record_submission_attempt(
+    /// Move a Ready proposal to Submitting and persist the transaction to submit.
+    ///
+    /// Both happen in one database transaction so a proposal is never left in
+    /// Submitting without an attempt the submission worker can pick up.
+    /// Any fee reservation is re-checked under a per-payer lock, so concurrent
+    /// submissions can't together lock more XRD than the payer holds.
+    /// Returns the new attempt's id.
+    pub async fn start_submission(
         &self,
         proposal_id: Uuid,
-        fee_payer_account: &str,
-        tx_hash: Option<&str>,
-        status: &str,
-        error_message: Option<&str>,
+        fee_reservation: Option<FeeReservation<'_>>,
+        composed: &ComposedTransaction,
+        fee_estimate: &str,
+    ) -> Result<Uuid> {
+        let mut tx = self.pool.begin().await?;
+
+        if let Some(reservation) = &fee_reservation {
+            sqlx::query("SELECT pg_advisory_xact_lock(hashtext('fee_payer'), hashtext($1))")
+                .bind(reservation.account)
+                .execute(&mut *tx)
+                .await?;
+
+            let locked: Vec<(Option<String>,)> = sqlx::query_as(
+                r#"
+                SELECT fee_locked FROM submission_attempts
+                WHERE fee_payer_account = $1 AND status IN ('pending', 'submitted')
+                  AND notarized_transaction_hex IS NOT NULL
+                "#,
+            )
+            .bind(reservation.account)
+            .fetch_all(&mut *tx)
+            .await?;
+            let reserved = locked
+                .into_iter()
+                .filter_map(|(amount,)| amount?.parse::<Decimal>().ok())
+                .fold(Decimal::ZERO, |sum, amount| sum + amount);
+            let available = reservation.balance - reserved;
+            if available < reservation.amount {
+                return Err(anyhow!(
+                    "Fee payer {} cannot cover a fee lock of {} XRD ({available} XRD available)",
+                    reservation.account,
+                    reservation.amount
+                ));
+            }
+        }
+
+        let result = sqlx::query(
+            "UPDATE proposals SET status = 'submitting' WHERE id = $1 AND status = 'ready'",
+        )
+        .bind(proposal_id)
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(anyhow!(
+                "Proposal {proposal_id} not found or not in Ready status"
+            ));
+        }
+
+        let (attempt_id,): (Uuid,) = sqlx::query_as(
+            r#"
+            INSERT INTO submission_attempts (proposal_id, fee_payer_account, tx_hash, status, notarized_transaction_hex, fee_estimate, fee_locked, end_epoch_exclusive)
+            VALUES ($1, $2, $3, 'pending', $4, $5, $6, $7)
+            RETURNING id
+            "#,
+        )
+        .bind(proposal_id)
+        .bind(fee_reservation.as_ref().map(|r| r.account))
+        .bind(&composed.intent_hash)
+        .bind(&composed.notarized_transaction_hex)
+        .bind(fee_estimate)
+        .bind(fee_reservation.as_ref().map(|r| r.amount.to_string()))
+        .bind(composed.end_epoch_exclusive as i64)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(attempt_id)
+    }
+
+    /// Record an attempt that failed before a transaction could be persisted
+    /// (e.g. composition failed during auto-submit). The proposal is left as is.
+    pub async fn record_failed_submission(
+        &self,
+        proposal_id: Uuid,
+        fee_payer_account: Option<&str>,
+        reason: FailureReason,
+        error_message: &str,
     ) -> Result<()> {
         sqlx::query(
             r#"
-            INSERT INTO submission_attempts (proposal_id, fee_payer_account, tx_hash, status, error_message)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO submission_attempts (proposal_id, fee_payer_account, status, failure_reason, error_message)
+            VALUES ($1, $2, 'failed', $3, $4)
             "#,
         )
         .bind(proposal_id)
         .bind(fee_payer_account)
-        .bind(tx_hash)
+        .bind(reason)
+        .bind(error_message)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Whether any submission attempt for this proposal has committed.
+    pub async fn has_committed_attempt(&self, proposal_id: Uuid) -> Result<bool> {
+        let (exists,): (bool,) = sqlx::query_as(
+            "SELECT EXISTS (SELECT 1 FROM submission_attempts WHERE proposal_id = $1 AND status = 'committed')",
+        )
+        .bind(proposal_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(exists)
+    }
+
+    /// Move a Failed proposal back to Ready so it can be resubmitted.
+    ///
+    /// Only succeeds while the subintent is still within its epoch window and
+    /// no attempt has committed; both are re-checked in the same statement.
+    pub async fn reopen_failed(&self, id: Uuid, current_epoch: u64) -> Result<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE proposals SET status = 'ready'
+            WHERE id = $1 AND status = 'failed' AND epoch_max > $2
+              AND NOT EXISTS (
+                  SELECT 1 FROM submission_attempts WHERE proposal_id = $1 AND status = 'committed'
+              )
+            "#,
+        )
+        .bind(id)
+        .bind(current_epoch as i64)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(anyhow!("Proposal {id} can no longer be retried"));
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_submission_attempt(&self, id: Uuid) -> Result<Option<SubmissionAttempt>> {
+        let row = sqlx::query_as::<_, SubmissionAttempt>(&format!(
+            r#"
+            SELECT {SUBMISSION_ATTEMPT_COLUMNS}
+            FROM submission_attempts
+            WHERE id = $1
+            "#,
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row)
+    }
+
+    /// List attempts the submission worker has not finished (pending or submitted).
+    pub async fn list_in_flight_submission_attempts(&self) -> Result<Vec<SubmissionAttempt>> {
+        let rows = sqlx::query_as::<_, SubmissionAttempt>(&format!(
+            r#"
+            SELECT {SUBMISSION_ATTEMPT_COLUMNS}
+            FROM submission_attempts
+            WHERE status IN ('pending', 'submitted') AND notarized_transaction_hex IS NOT NULL
+            ORDER BY created_at ASC
+            "#,
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Update the status of a submission attempt.
+    pub async fn update_submission_attempt(&self, id: Uuid, status: &str) -> Result<()> {
+        let result = sqlx::query(
+            "UPDATE submission_attempts SET status = $1, updated_at = NOW() WHERE id = $2",
+        )
         .bind(status)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(anyhow!("Submission attempt {id} not found"));
+        }
+
+        Ok(())
+    }
+
+    /// Record the fee a committed (successfully or not) attempt paid.
+    pub async fn record_fee_paid(&self, id: Uuid, fee_paid: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE submission_attempts SET fee_paid = $1, updated_at = NOW() WHERE id = $2",
+        )
+        .bind(fee_paid)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Mark a submission attempt as failed with a classified reason.
+    pub async fn fail_submission_attempt(
+        &self,
+        id: Uuid,
+        reason: FailureReason,
+        error_message: &str,
+    ) -> Result<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE submission_attempts
+            SET status = 'failed', failure_reason = $1, error_message = $2, updated_at = NOW()
+            WHERE id = $3
+            "#,
+        )
+        .bind(reason)
         .bind(error_message)
+        .bind(id)
         .execute(&self.pool)
         .await?;
 
+        if result.rows_affected() == 0 {
+            return Err(anyhow!("Submission attempt {id} not found"));
+        }
+
         Ok(())
     }
 
+    /// List every submission attempt for a proposal, oldest first.
+    pub async fn list_submission_attempts(
+        &self,
+        proposal_id: Uuid,
+    ) -> Result<Vec<SubmissionAttempt>> {
+        let rows = sqlx::query_as::<_, SubmissionAttempt>(&format!(
+            r#"
+            SELECT {SUBMISSION_ATTEMPT_COLUMNS}
+            FROM submission_attempts
+            WHERE proposal_id = $1
+            ORDER BY created_at ASC
+            "#,
+        ))
+        .bind(proposal_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Average XRD fee paid by the most recent `limit` attempts that recorded one.
+    pub async fn recent_average_fee_paid(&self, limit: i64) -> Result<Option<String>> {
+        let (average,): (Option<String>,) = sqlx::query_as(
+            r#"
+            SELECT ROUND(AVG(fee_paid::NUMERIC), 18)::TEXT
+            FROM (
+                SELECT fee_paid
+                FROM submission_attempts
+                WHERE fee_paid IS NOT NULL
+                ORDER BY updated_at DESC
+                LIMIT $1
+            ) recent
+            "#,
+        )
+        .bind(limit)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(average)
+    }
+
     /// List proposals in active states (Created, Signing, Ready) for validity monitoring.
     pub async fn list_active(&self) -> Result<Vec<Proposal>> {
-        let rows = sqlx::query_as::<_, Proposal>(
+        let rows = sqlx::query_as::<_, Proposal>(&format!(
             r#"
-            SELECT id, manifest_text, multisig_account, epoch_min, epoch_max,
-                   status, subintent_hash, intent_discriminator, min_proposer_timestamp, max_proposer_timestamp,
-                   created_at, submitted_at, tx_id, invalid_reason
+            SELECT {PROPOSAL_COLUMNS}
             FROM proposals
             WHERE status IN ('created', 'signing', 'ready')
             ORDER BY created_at ASC
             "#,
-        )
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Hand a Ready proposal over to an external submitter. Idempotent: the
+    /// first export's timestamp is kept.
+    pub async fn mark_externally_submitted(&self, id: Uuid) -> Result<Proposal> {
+        sqlx::query_as::<_, Proposal>(&format!(
+            r#"
+            UPDATE proposals
+            SET externally_submitted_at = COALESCE(externally_submitted_at, NOW())
+            WHERE id = $1 AND status = 'ready'
+            RETURNING {PROPOSAL_COLUMNS}
+            "#,
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| anyhow!("Proposal {id} not found or not in Ready status"))
+    }
+
+    /// Proposals whose subintent may already have been committed on ledger.
+    pub async fn list_awaiting_commit(&self) -> Result<Vec<Proposal>> {
+        let rows = sqlx::query_as::<_, Proposal>(&format!(
+            r#"
+            SELECT {PROPOSAL_COLUMNS}
+            FROM proposals
+            WHERE status IN ('signing', 'ready', 'submitting', 'failed')
+            ORDER BY created_at ASC
+            "#,
+        ))
         .fetch_all(&self.pool)
         .await?;
 
         Ok(rows)
     }
 
+    /// Proposals stuck in Submitting, oldest first.
+    pub async fn list_submitting(&self) -> Result<Vec<Proposal>> {
+        let rows = sqlx::query_as::<_, Proposal>(&format!(
+            r#"
+            SELECT {PROPOSAL_COLUMNS}
+            FROM proposals
+            WHERE status = 'submitting'
+            ORDER BY created_at ASC
+            "#,
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Close a proposal whose subintent was committed on ledger by a
+    /// transaction the server did not submit, or by one of its own attempts
+    /// that was failed before the commit showed up. That attempt is marked
+    /// committed; any other in-flight attempts can no longer commit, so they
+    /// are failed.
+    pub async fn mark_committed_on_ledger(&self, id: Uuid, tx_id: Option<&str>) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE proposals
+            SET status = 'committed', tx_id = COALESCE($2, tx_id), submitted_at = COALESCE(submitted_at, NOW())
+            WHERE id = $1 AND status IN ('signing', 'ready', 'submitting', 'failed')
+            "#,
+        )
+        .bind(id)
+        .bind(tx_id)
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(anyhow!("Proposal {id} not found or not awaiting a commit"));
+        }
+
+        if let Some(tx_id) = tx_id {
+            sqlx::query(
+                r#"
+                UPDATE submission_attempts
+                SET status = 'committed', failure_reason = NULL, error_message = NULL, updated_at = NOW()
+                WHERE proposal_id = $1 AND tx_hash = $2
+                "#,
+            )
+            .bind(id)
+            .bind(tx_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE submission_attempts
+            SET status = 'failed', failure_reason = $2, error_message = $3, updated_at = NOW()
+            WHERE proposal_id = $1 AND status IN ('pending', 'submitted')
+            "#,
+        )
+        .bind(id)
+        .bind(FailureReason::SubintentAlreadyCommitted)
+        .bind("Subintent was committed by another transaction")
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
     /// Mark a proposal as expired (epoch window passed).
     pub async fn mark_expired(&self, id: Uuid) -> Result<()> {
         let result = sqlx::query(
@@ -245,6 +757,27 @@ impl ProposalStore {
         Ok(())
     }
 
+    /// Cancel a proposal that has not been submitted, recording who cancelled it and why.
+    /// A proposal handed over for external submission may still commit, so it
+    /// can't be cancelled.
+    pub async fn cancel(&self, id: Uuid, cancelled_by: &str, reason: &str) -> Result<Proposal> {
+        sqlx::query_as::<_, Proposal>(&format!(
+            r#"
+            UPDATE proposals
+            SET status = 'cancelled', cancelled_by = $1, cancel_reason = $2, cancelled_at = NOW()
+            WHERE id = $3 AND status IN ('created', 'signing', 'ready')
+              AND externally_submitted_at IS NULL
+            RETURNING {PROPOSAL_COLUMNS}
+            "#,
+        ))
+        .bind(cancelled_by)
+        .bind(reason)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| anyhow!("Proposal {id} not found or not in a cancellable status"))
+    }
+
     /// Flag a signature as invalid (signer removed from access rule).
     pub async fn invalidate_signature(
         &self,
@@ -262,10 +795,59 @@ impl ProposalStore {
         Ok(())
     }
 
-    /// Get (key_hash, is_valid) pairs for all signatures on a proposal.
-    pub async fn get_signature_key_hashes(&self, proposal_id: Uuid) -> Result<Vec<(String, bool)>> {
-        let rows: Vec<(String, bool)> = sqlx::query_as(
-            "SELECT signer_key_hash, is_valid FROM signatures WHERE proposal_id = $1",
+    /// Count a previously invalidated signature again (signer re-added to the access rule).
+    pub async fn revalidate_signature(
+        &self,
+        proposal_id: Uuid,
+        signer_key_hash: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            "UPDATE signatures SET is_valid = TRUE WHERE proposal_id = $1 AND signer_key_hash = $2",
+        )
+        .bind(proposal_id)
+        .bind(signer_key_hash)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record a validity monitor decision about a proposal.
+    pub async fn record_validity_decision(
+        &self,
+        proposal_id: Uuid,
+        decision: ValidityDecisionKind,
+        signer_key_hash: Option<&str>,
+        reason: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO validity_decisions (proposal_id, decision, signer_key_hash, reason)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(proposal_id)
+        .bind(decision)
+        .bind(signer_key_hash)
+        .bind(reason)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Validity monitor decisions about a proposal, oldest first.
+    pub async fn list_validity_decisions(
+        &self,
+        proposal_id: Uuid,
+    ) -> Result<Vec<ValidityDecision>> {
+        let rows = sqlx::query_as::<_, ValidityDecision>(
+            r#"
+            SELECT id, proposal_id, decision, signer_key_hash, reason, created_at
+            FROM validity_decisions
+            WHERE proposal_id = $1
+            ORDER BY created_at ASC
+            "#,
         )
         .bind(proposal_id)
         .fetch_all(&self.pool)
@@ -274,15 +856,16 @@ impl ProposalStore {
         Ok(rows)
     }
 
-    /// Count valid signatures for a proposal.
-    pub async fn count_valid_signatures(&self, proposal_id: Uuid) -> Result<i64> {
-        let row: (i64,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM signatures WHERE proposal_id = $1 AND is_valid = TRUE",
+    /// Get (key_hash, is_valid) pairs for all signatures on a proposal.
+    pub async fn get_signature_key_hashes(&self, proposal_id: Uuid) -> Result<Vec<(String, bool)>> {
+        let rows: Vec<(String, bool)> = sqlx::query_as(
+            "SELECT signer_key_hash, is_valid FROM signatures WHERE proposal_id = $1",
         )
         .bind(proposal_id)
-        .fetch_one(&self.pool)
+        .fetch_all(&self.pool)
         .await?;
-        Ok(row.0)
+
+        Ok(rows)
     }
 
     pub async fn transition_status(
@@ -290,6 +873,19 @@ impl ProposalStore {
         id: Uuid,
         from: ProposalStatus,
         to: ProposalStatus,
+    ) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        self.transition_status_on(&mut conn, id, from, to).await
+    }
+
+    /// [`Self::transition_status`] on a given connection, e.g. inside a
+    /// transaction that holds the proposal's row lock.
+    pub async fn transition_status_on(
+        &self,
+        conn: &mut PgConnection,
+        id: Uuid,
+        from: ProposalStatus,
+        to: ProposalStatus,
     ) -> Result<()> {
         if !from.can_transition_to(to) {
             return Err(anyhow!("Invalid status transition: {from:?} → {to:?}"));
@@ -299,7 +895,7 @@ impl ProposalStore {
             .bind(to)
             .bind(id)
             .bind(from)
-            .execute(&self.pool)
+            .execute(conn)
             .await?;
 
         if result.rows_affected() == 0 {
@@ -323,6 +919,21 @@ mod tests {
         assert!(ProposalStatus::Submitting.can_transition_to(ProposalStatus::Failed));
     }
 
+    #[test]
+    fn readiness_can_be_lost() {
+        assert!(ProposalStatus::Ready.can_transition_to(ProposalStatus::Signing));
+        assert!(!ProposalStatus::Submitting.can_transition_to(ProposalStatus::Signing));
+    }
+
+    #[test]
+    fn out_of_band_commit_transitions() {
+        assert!(ProposalStatus::Signing.can_transition_to(ProposalStatus::Committed));
+        assert!(ProposalStatus::Ready.can_transition_to(ProposalStatus::Committed));
+        assert!(!ProposalStatus::Created.can_transition_to(ProposalStatus::Committed));
+        // A failed attempt's transaction can still commit afterwards
+        assert!(ProposalStatus::Failed.can_transition_to(ProposalStatus::Committed));
+    }
+
     #[test]
     fn expiry_transitions() {
         assert!(ProposalStatus::Created.can_transition_to(ProposalStatus::Expired));
@@ -337,6 +948,23 @@ mod tests {
         assert!(ProposalStatus::Ready.can_transition_to(ProposalStatus::Invalid));
     }
 
+    #[test]
+    fn cancel_transitions() {
+        assert!(ProposalStatus::Created.can_transition_to(ProposalStatus::Cancelled));
+        assert!(ProposalStatus::Signing.can_transition_to(ProposalStatus::Cancelled));
+        assert!(ProposalStatus::Ready.can_transition_to(ProposalStatus::Cancelled));
+        assert!(!ProposalStatus::Submitting.can_transition_to(ProposalStatus::Cancelled));
+        assert!(!ProposalStatus::Committed.can_transition_to(ProposalStatus::Cancelled));
+        assert!(!ProposalStatus::Cancelled.can_transition_to(ProposalStatus::Signing));
+    }
+
+    #[test]
+    fn retry_transition() {
+        assert!(ProposalStatus::Failed.can_transition_to(ProposalStatus::Ready));
+        assert!(!ProposalStatus::Committed.can_transition_to(ProposalStatus::Ready));
+        assert!(!ProposalStatus::Expired.can_transition_to(ProposalStatus::Ready));
+    }
+
     #[test]
     fn rejects_invalid_transitions() {
         assert!(!ProposalStatus::Created.can_transition_to(ProposalStatus::Ready));
@@ -346,4 +974,135 @@ mod tests {
         assert!(!ProposalStatus::Expired.can_transition_to(ProposalStatus::Created));
         assert!(!ProposalStatus::Submitting.can_transition_to(ProposalStatus::Signing));
     }
+
+    async fn ready_proposal(proposal_store: &ProposalStore) -> Proposal {
+        let proposal = proposal_store
+            .create(CreateProposal {
+                manifest_text: "YIELD_TO_PARENT;".into(),
+                multisig_account: "account_tdx_2_test".into(),
+                epoch_min: 1000,
+                epoch_max: 1100,
+                subintent_hash: Uuid::new_v4().to_string(),
+                intent_discriminator: 12345,
+                min_proposer_timestamp: 0,
+                max_proposer_timestamp: 0,
+                partial_transaction_bytes: Vec::new(),
+                proposer_key_hash: None,
+                auto_submit: false,
+                preview: None,
+                fee_mode: Default::default(),
+                reimbursed_fee_payer: None,
+                fee_reimbursement: None,
+            })
+            .await
+            .unwrap();
+        for (from, to) in [
+            (ProposalStatus::Created, ProposalStatus::Signing),
+            (ProposalStatus::Signing, ProposalStatus::Ready),
+        ] {
+            proposal_store
+                .transition_status(proposal.id, from, to)
+                .await
+                .unwrap();
+        }
+        proposal_store.get(proposal.id).await.unwrap().unwrap()
+    }
+
+    /// Needs a Postgres database: `DATABASE_URL=... cargo test -- --ignored`.
+    #[test]
+    #[ignore]
+    fn integration_externally_submitted_proposal_cannot_be_cancelled() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        rt.block_on(async {
+            let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+            let pool = PgPool::connect(&database_url).await.unwrap();
+            sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+            let proposal_store = ProposalStore::new(pool);
+
+            let handed_over = ready_proposal(&proposal_store).await;
+            proposal_store
+                .mark_externally_submitted(handed_over.id)
+                .await
+                .unwrap();
+            assert!(proposal_store
+                .cancel(handed_over.id, "signer", "changed my mind")
+                .await
+                .is_err());
+            let handed_over = proposal_store.get(handed_over.id).await.unwrap().unwrap();
+            assert_eq!(handed_over.status, ProposalStatus::Ready);
+
+            let kept = ready_proposal(&proposal_store).await;
+            let cancelled = proposal_store
+                .cancel(kept.id, "signer", "changed my mind")
+                .await
+                .unwrap();
+            assert_eq!(cancelled.status, ProposalStatus::Cancelled);
+        });
+    }
+
+    /// Two submissions race for a fee payer that can only cover one of them.
+    /// Needs a Postgres database: `DATABASE_URL=... cargo test -- --ignored`.
+    #[test]
+    #[ignore]
+    fn integration_concurrent_submissions_cannot_overcommit_fee_payer() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        rt.block_on(async {
+            let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+            let pool = PgPool::connect(&database_url).await.unwrap();
+            sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+            let proposal_store = std::sync::Arc::new(ProposalStore::new(pool));
+
+            let account = format!("account_tdx_2_{}", Uuid::new_v4().simple());
+            let proposals = [
+                ready_proposal(&proposal_store).await,
+                ready_proposal(&proposal_store).await,
+            ];
+
+            let tasks: Vec<_> = proposals
+                .iter()
+                .map(|proposal| {
+                    let proposal_store = proposal_store.clone();
+                    let account = account.clone();
+                    let proposal_id = proposal.id;
+                    tokio::spawn(async move {
+                        let composed = ComposedTransaction {
+                            notarized_transaction_hex: "00".into(),
+                            intent_hash: format!("txid_tdx_2_{}", Uuid::new_v4().simple()),
+                            end_epoch_exclusive: 1100,
+                        };
+                        proposal_store
+                            .start_submission(
+                                proposal_id,
+                                Some(FeeReservation {
+                                    account: &account,
+                                    amount: Decimal::from(6u32),
+                                    balance: Decimal::from(10u32),
+                                }),
+                                &composed,
+                                "5",
+                            )
+                            .await
+                    })
+                })
+                .collect();
+            let mut started = 0;
+            for task in tasks {
+                if task.await.unwrap().is_ok() {
+                    started += 1;
+                }
+            }
+            assert_eq!(started, 1);
+
+            let mut statuses = Vec::new();
+            for proposal in &proposals {
+                let proposal = proposal_store.get(proposal.id).await.unwrap().unwrap();
+                statuses.push(proposal.status);
+            }
+            statuses.sort_by_key(|status| format!("{status:?}"));
+            assert_eq!(
+                statuses,
+                vec![ProposalStatus::Ready, ProposalStatus::Submitting]
+            );
+        });
+    }
 }
@@ -2,13 +2,15 @@ use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
 use radix_common::prelude::*;
 use radix_transactions::prelude::*;
+use radix_transactions::validation::{verify, verify_and_recover};
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::gateway::{AccessRuleInfo, SignerInfo};
 use crate::proposal_store::{ProposalStatus, ProposalStore};
+use crate::submission_worker::SubmissionQueue;
 
 /// Compute the bech32-encoded root subintent hash from a signed partial transaction hex.
 ///
@@ -53,6 +55,7 @@ pub struct Signature {
     pub proposal_id: Uuid,
     pub signer_public_key: String,
     pub signer_key_hash: String,
+    pub signer_key_type: String,
     pub signature_bytes: Vec<u8>,
     pub signed_partial_transaction_hex: String,
     pub created_at: DateTime<Utc>,
@@ -66,15 +69,27 @@ pub struct SignatureStatus {
     pub signatures: Vec<SignatureSummary>,
     pub threshold: u8,
     pub collected: usize,
+    /// Whether the valid signatures satisfy the access rule.
+    pub satisfied: bool,
+    /// Signatures still needed: zero once satisfied, otherwise the cheapest
+    /// way to meet every unmet branch of the rule.
     pub remaining: usize,
     pub signers: Vec<SignerStatus>,
+    /// Per-signature outcomes of the submission that produced this status.
+    /// Only populated in responses to `POST /proposals/{id}/sign`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outcomes: Vec<SignatureOutcome>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SignatureSummary {
     pub signer_public_key: String,
     pub signer_key_hash: String,
+    pub signer_key_type: String,
     pub created_at: DateTime<Utc>,
+    /// False once the signer has left the access rule; the signature then
+    /// doesn't count towards readiness and isn't used for submission.
+    pub is_valid: bool,
 }
 
 /// Per-signer status: have they signed or not, and is their signature still valid?
@@ -86,18 +101,55 @@ pub struct SignerStatus {
     pub is_valid: bool,
 }
 
+/// What happened to one signature from a submitted signed partial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureOutcomeKind {
+    Accepted,
+    Duplicate,
+    NotInAccessRule,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SignatureOutcome {
+    pub signer_key_hash: String,
+    pub key_type: String,
+    pub outcome: SignatureOutcomeKind,
+}
+
+impl SignatureOutcome {
+    fn describe(&self) -> String {
+        match self.outcome {
+            SignatureOutcomeKind::Accepted => format!("signer {} accepted", self.signer_key_hash),
+            SignatureOutcomeKind::Duplicate => format!(
+                "signer {} has already signed this proposal",
+                self.signer_key_hash
+            ),
+            SignatureOutcomeKind::NotInAccessRule => format!(
+                "signer with key hash {} is not in the current access rule",
+                self.signer_key_hash
+            ),
+        }
+    }
+}
+
 pub struct SignatureCollector {
     pool: PgPool,
+    submission_queue: SubmissionQueue,
 }
 
-/// Extract signature + public key from a signed partial transaction hex string.
+/// Extract every root subintent signature + public key from a signed partial transaction hex string.
 ///
 /// The wallet's `sendPreAuthorizationRequest` returns a hex-encoded
-/// `SignedPartialTransactionV2`. We decode it and pull out the first
-/// signature (which includes the Ed25519 public key).
-pub fn extract_signature_from_hex(
+/// `SignedPartialTransactionV2`. We decode it and verify each root subintent
+/// signature against the root subintent hash — a single operator holding
+/// several signer keys, or a co-signing tool, can put more than one there.
+/// Ed25519 signatures carry their public key; for Secp256k1 the public key is
+/// recovered from the recoverable signature. Returns an "Invalid signature"
+/// error if any signature does not verify.
+pub fn extract_signatures_from_hex(
     signed_partial_hex: &str,
-) -> Result<(SignatureWithPublicKeyV1, String)> {
+) -> Result<Vec<(SignatureWithPublicKeyV1, String)>> {
     let bytes = hex::decode(signed_partial_hex).map_err(|e| anyhow!("Invalid hex: {e}"))?;
 
     let raw = RawSignedPartialTransaction::from_vec(bytes);
@@ -109,41 +161,128 @@ pub fn extract_signature_from_hex(
         return Err(anyhow!("Signed partial transaction has no signatures"));
     }
 
-    let sig = &signatures[0].0;
-    let public_key_hex = match sig {
-        SignatureWithPublicKeyV1::Ed25519 { public_key, .. } => hex::encode(public_key.0),
-        SignatureWithPublicKeyV1::Secp256k1 { .. } => {
-            return Err(anyhow!(
-                "Secp256k1 signatures not yet supported (public key recovery needed)"
-            ));
-        }
-    };
+    let prepared = signed_partial
+        .prepare(PreparationSettings::latest_ref())
+        .map_err(|e| anyhow!("Failed to prepare signed partial transaction: {e:?}"))?;
+    let subintent_hash = prepared.subintent_hash();
+
+    signatures
+        .iter()
+        .map(|sig| {
+            let public_key = verify_signature(&subintent_hash, &sig.0)?;
+            Ok((sig.0, public_key_to_hex(&public_key)))
+        })
+        .collect()
+}
 
-    Ok((sig.clone(), public_key_hex))
+/// Verify a signature over a subintent hash, returning the signer's public key.
+///
+/// Ed25519 signatures are checked against their embedded public key; Secp256k1
+/// signatures are verified as part of public key recovery.
+pub fn verify_signature(
+    subintent_hash: &SubintentHash,
+    sig: &SignatureWithPublicKeyV1,
+) -> Result<PublicKey> {
+    verify_and_recover(subintent_hash.as_hash(), sig).ok_or_else(|| match sig {
+        SignatureWithPublicKeyV1::Ed25519 { public_key, .. } => anyhow!(
+            "Invalid signature: Ed25519 signature from {} does not verify against the subintent hash",
+            hex::encode(public_key.0)
+        ),
+        SignatureWithPublicKeyV1::Secp256k1 { .. } => anyhow!(
+            "Invalid signature: Secp256k1 signature does not verify against the subintent hash"
+        ),
+    })
+}
+
+/// Hex-encode the raw bytes of an Ed25519 or compressed Secp256k1 public key.
+fn public_key_to_hex(public_key: &PublicKey) -> String {
+    match public_key {
+        PublicKey::Ed25519(pk) => hex::encode(pk.0),
+        PublicKey::Secp256k1(pk) => hex::encode(pk.0),
+    }
 }
 
-/// Compute the key hash from an Ed25519 public key hex string.
+/// Key type name for a signature, matching `SignerInfo::key_type` from the Gateway.
+pub fn signature_key_type(sig: &SignatureWithPublicKeyV1) -> &'static str {
+    match sig {
+        SignatureWithPublicKeyV1::Ed25519 { .. } => "EddsaEd25519",
+        SignatureWithPublicKeyV1::Secp256k1 { .. } => "EcdsaSecp256k1",
+    }
+}
+
+/// Compute the key hash from an Ed25519 or compressed Secp256k1 public key hex string.
 ///
-/// Returns the hex-encoded hash that matches what the Gateway API returns
-/// in the access rule's NonFungibleGlobalId local_id simple_rep.
+/// The curve is inferred from the key length (32 bytes for Ed25519, 33 for
+/// Secp256k1). Returns the hex-encoded hash that matches what the Gateway API
+/// returns in the access rule's NonFungibleGlobalId local_id simple_rep.
 pub fn compute_key_hash(public_key_hex: &str) -> Result<String> {
+    let pk = parse_public_key_hex(public_key_hex)?;
+    let hash = pk.get_hash();
+
+    Ok(hex::encode(hash.get_hash_bytes()))
+}
+
+/// Decode an Ed25519 (32 bytes) or compressed Secp256k1 (33 bytes) public key hex string.
+pub fn parse_public_key_hex(public_key_hex: &str) -> Result<PublicKey> {
     let pk_bytes =
         hex::decode(public_key_hex).map_err(|e| anyhow!("Invalid public key hex: {e}"))?;
 
-    if pk_bytes.len() != Ed25519PublicKey::LENGTH {
+    let pk: PublicKey = match pk_bytes.len() {
+        Ed25519PublicKey::LENGTH => Ed25519PublicKey::try_from(pk_bytes.as_slice())
+            .map_err(|e| anyhow!("Invalid Ed25519 public key: {e:?}"))?
+            .into(),
+        Secp256k1PublicKey::LENGTH => Secp256k1PublicKey::try_from(pk_bytes.as_slice())
+            .map_err(|e| anyhow!("Invalid Secp256k1 public key: {e:?}"))?
+            .into(),
+        other => {
+            return Err(anyhow!(
+                "Invalid public key length: {other} (expected {} for Ed25519 or {} for Secp256k1)",
+                Ed25519PublicKey::LENGTH,
+                Secp256k1PublicKey::LENGTH
+            ))
+        }
+    };
+
+    Ok(pk)
+}
+
+/// The hash a proposer or signer signs to authorize cancelling a proposal.
+///
+/// Blake2b-256 of `multisig-cancel:{proposal_id}:{subintent_hash}`, so an
+/// authorization for one proposal cannot be replayed against another.
+pub fn cancellation_message_hash(proposal_id: Uuid, subintent_hash: &str) -> Hash {
+    hash(format!("multisig-cancel:{proposal_id}:{subintent_hash}"))
+}
+
+/// Verify a detached signature over `message_hash`, returning the signer's key hash.
+///
+/// The signature is 64 bytes for an Ed25519 key or 65 bytes (recoverable) for
+/// a Secp256k1 key; the curve follows from the public key.
+pub fn verify_message_signature(
+    message_hash: &Hash,
+    public_key_hex: &str,
+    signature_hex: &str,
+) -> Result<String> {
+    let public_key = parse_public_key_hex(public_key_hex)?;
+    let sig_bytes =
+        hex::decode(signature_hex).map_err(|e| anyhow!("Invalid signature hex: {e}"))?;
+
+    let signature: SignatureV1 = match public_key {
+        PublicKey::Ed25519(_) => Ed25519Signature::try_from(sig_bytes.as_slice())
+            .map_err(|e| anyhow!("Invalid signature: bad Ed25519 signature: {e:?}"))?
+            .into(),
+        PublicKey::Secp256k1(_) => Secp256k1Signature::try_from(sig_bytes.as_slice())
+            .map_err(|e| anyhow!("Invalid signature: bad Secp256k1 signature: {e:?}"))?
+            .into(),
+    };
+
+    if !verify(message_hash, &public_key, &signature) {
         return Err(anyhow!(
-            "Invalid Ed25519 public key length: {} (expected {})",
-            pk_bytes.len(),
-            Ed25519PublicKey::LENGTH
+            "Invalid signature: signature from {public_key_hex} does not verify"
         ));
     }
 
-    let mut arr = [0u8; Ed25519PublicKey::LENGTH];
-    arr.copy_from_slice(&pk_bytes);
-    let pk = Ed25519PublicKey(arr);
-    let hash = pk.get_hash();
-
-    Ok(hex::encode(hash.get_hash_bytes()))
+    Ok(hex::encode(public_key.get_hash().get_hash_bytes()))
 }
 
 /// Check whether a key hash matches any signer in the access rule.
@@ -154,6 +293,23 @@ pub fn find_signer_by_hash<'a>(
     access_rule.signers.iter().find(|s| s.key_hash == key_hash)
 }
 
+/// Whether the valid signatures satisfy the access rule, and how many more
+/// it needs if not.
+pub fn signature_progress(
+    access_rule: &AccessRuleInfo,
+    valid_key_hashes: &[&str],
+) -> (bool, usize) {
+    if access_rule.is_satisfied_by(valid_key_hashes.iter().copied()) {
+        return (true, 0);
+    }
+    // A rule signatures can never satisfy still needs at least one more
+    let remaining = access_rule
+        .signatures_needed(valid_key_hashes.iter().copied())
+        .unwrap_or_else(|| (access_rule.threshold as usize).saturating_sub(valid_key_hashes.len()))
+        .max(1);
+    (false, remaining)
+}
+
 /// Encode signature bytes for storage.
 fn encode_signature_bytes(sig: &SignatureWithPublicKeyV1) -> Vec<u8> {
     match sig {
@@ -163,19 +319,33 @@ fn encode_signature_bytes(sig: &SignatureWithPublicKeyV1) -> Vec<u8> {
 }
 
 impl SignatureCollector {
-    pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+    pub fn new(pool: PgPool, submission_queue: SubmissionQueue) -> Self {
+        Self {
+            pool,
+            submission_queue,
+        }
     }
 
-    /// Add a signature for a proposal. Returns the updated signature status.
+    /// Add every signature in a signed partial to a proposal. Returns the
+    /// updated signature status with a per-signature outcome.
     ///
     /// Validates:
     /// 1. Wallet signed over the correct subintent (hash match)
-    /// 2. Signer is in the current access rule
-    /// 3. No duplicate signature from the same signer
-    /// 4. Proposal is in a valid state (Created or Signing)
+    /// 2. Every signature is cryptographically valid for that subintent hash
+    /// 3. Proposal is in a valid state (Created or Signing)
     ///
-    /// Transitions: Created→Signing on first sig, Signing→Ready when threshold met.
+    /// Each signature is then recorded as accepted, a duplicate of an existing
+    /// signature from the same signer, or rejected because the signer is not in
+    /// the current access rule. Fails if none were accepted.
+    ///
+    /// Transitions (once, after the whole batch): Created→Signing when anything
+    /// was accepted, Signing→Ready when the collected signers satisfy the
+    /// access rule tree. Reaching Ready enqueues submission for proposals
+    /// with `auto_submit` set.
+    ///
+    /// The status check, inserts and transitions run in one transaction that
+    /// holds the proposal's row lock, so concurrent signers, cancellation and
+    /// the validity monitor see each other's changes in order.
     pub async fn add_signature(
         &self,
         proposal_id: Uuid,
@@ -197,20 +367,14 @@ impl SignatureCollector {
             ));
         }
 
-        // Extract signature + public key from the wallet's response
-        let (sig, public_key_hex) = extract_signature_from_hex(signed_partial_hex)?;
-        let key_hash = compute_key_hash(&public_key_hex)?;
+        // Extract and verify every signature + public key from the wallet's response
+        let extracted = extract_signatures_from_hex(signed_partial_hex)?;
 
-        // Validate signer is in the access rule
-        if find_signer_by_hash(access_rule, &key_hash).is_none() {
-            return Err(anyhow!(
-                "Signer with key hash {key_hash} is not in the current access rule"
-            ));
-        }
+        let mut tx = self.pool.begin().await?;
 
         // Check proposal exists and is in valid state
         let proposal = proposal_store
-            .get(proposal_id)
+            .get_for_update(&mut tx, proposal_id)
             .await?
             .ok_or_else(|| anyhow!("Proposal {proposal_id} not found"))?;
 
@@ -222,39 +386,59 @@ impl SignatureCollector {
             ));
         }
 
-        // Store the signature (UNIQUE constraint prevents duplicates)
-        let sig_bytes = encode_signature_bytes(&sig);
-        let result = sqlx::query(
-            r#"
-            INSERT INTO signatures (proposal_id, signer_public_key, signer_key_hash, signature_bytes, signed_partial_transaction_hex)
-            VALUES ($1, $2, $3, $4, $5)
-            "#,
-        )
-        .bind(proposal_id)
-        .bind(&public_key_hex)
-        .bind(&key_hash)
-        .bind(&sig_bytes)
-        .bind(signed_partial_hex)
-        .execute(&self.pool)
-        .await;
-
-        match result {
-            Ok(_) => {}
-            Err(sqlx::Error::Database(db_err)) if db_err.is_unique_violation() => {
-                return Err(anyhow!(
-                    "Signer {key_hash} has already signed this proposal"
-                ));
-            }
-            Err(e) => return Err(e.into()),
+        let mut outcomes = Vec::with_capacity(extracted.len());
+        for (sig, public_key_hex) in &extracted {
+            let key_hash = compute_key_hash(public_key_hex)?;
+            let key_type = signature_key_type(sig);
+
+            // Validate signer is in the access rule
+            let outcome = if find_signer_by_hash(access_rule, &key_hash).is_none() {
+                SignatureOutcomeKind::NotInAccessRule
+            } else if self
+                .insert_signature(
+                    &mut tx,
+                    proposal_id,
+                    public_key_hex,
+                    &key_hash,
+                    sig,
+                    signed_partial_hex,
+                )
+                .await?
+            {
+                SignatureOutcomeKind::Accepted
+            } else {
+                SignatureOutcomeKind::Duplicate
+            };
+
+            outcomes.push(SignatureOutcome {
+                signer_key_hash: key_hash,
+                key_type: key_type.to_string(),
+                outcome,
+            });
         }
 
-        // Count signatures and handle state transitions
-        let sig_count = self.count_signatures(proposal_id).await?;
+        if !outcomes
+            .iter()
+            .any(|o| o.outcome == SignatureOutcomeKind::Accepted)
+        {
+            return Err(anyhow!(
+                "No signatures accepted: {}",
+                outcomes
+                    .iter()
+                    .map(SignatureOutcome::describe)
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ));
+        }
+
+        // Evaluate collected signatures against the rule and handle state transitions
+        let signed_key_hashes = self.signed_key_hashes(&mut tx, proposal_id).await?;
 
         // Created → Signing on first signature
         if proposal.status == ProposalStatus::Created {
             proposal_store
-                .transition_status(
+                .transition_status_on(
+                    &mut tx,
                     proposal_id,
                     ProposalStatus::Created,
                     ProposalStatus::Signing,
@@ -262,14 +446,72 @@ impl SignatureCollector {
                 .await?;
         }
 
-        // Signing → Ready when threshold met
-        if sig_count >= access_rule.threshold as i64 {
+        // Signing → Ready when the access rule is satisfied
+        let ready = access_rule.is_satisfied_by(signed_key_hashes.iter().map(String::as_str));
+        if ready {
             proposal_store
-                .transition_status(proposal_id, ProposalStatus::Signing, ProposalStatus::Ready)
+                .transition_status_on(
+                    &mut tx,
+                    proposal_id,
+                    ProposalStatus::Signing,
+                    ProposalStatus::Ready,
+                )
                 .await?;
         }
 
-        self.get_signature_status(proposal_id, access_rule).await
+        tx.commit().await?;
+
+        if ready {
+            if let Err(e) = self
+                .submission_queue
+                .proposal_ready(proposal_id, proposal.auto_submit)
+            {
+                // Picked up again from the Ready state on the next startup
+                tracing::error!("Failed to enqueue auto-submit for {proposal_id}: {e}");
+            }
+        }
+
+        let mut status = self.get_signature_status(proposal_id, access_rule).await?;
+        status.outcomes = outcomes;
+        Ok(status)
+    }
+
+    /// Store one signature. Returns false if this signer already has a valid
+    /// signature on the proposal (UNIQUE constraint on proposal + key hash);
+    /// an invalidated one is replaced, as the caller checked the signer is in
+    /// the current access rule.
+    async fn insert_signature(
+        &self,
+        conn: &mut PgConnection,
+        proposal_id: Uuid,
+        public_key_hex: &str,
+        key_hash: &str,
+        sig: &SignatureWithPublicKeyV1,
+        signed_partial_hex: &str,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO signatures (proposal_id, signer_public_key, signer_key_hash, signer_key_type, signature_bytes, signed_partial_transaction_hex)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (proposal_id, signer_key_hash) DO UPDATE
+            SET signer_public_key = EXCLUDED.signer_public_key,
+                signer_key_type = EXCLUDED.signer_key_type,
+                signature_bytes = EXCLUDED.signature_bytes,
+                signed_partial_transaction_hex = EXCLUDED.signed_partial_transaction_hex,
+                is_valid = TRUE
+            WHERE NOT signatures.is_valid
+            "#,
+        )
+        .bind(proposal_id)
+        .bind(public_key_hex)
+        .bind(key_hash)
+        .bind(signature_key_type(sig))
+        .bind(encode_signature_bytes(sig))
+        .bind(signed_partial_hex)
+        .execute(conn)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
     }
 
     /// Get the current signature status for a proposal.
@@ -303,9 +545,13 @@ impl SignatureCollector {
             })
             .collect();
 
-        let collected = signatures.len();
-        let threshold = access_rule.threshold as usize;
-        let remaining = threshold.saturating_sub(collected);
+        let valid_key_hashes: Vec<&str> = signatures
+            .iter()
+            .filter(|s| s.is_valid)
+            .map(|s| s.signer_key_hash.as_str())
+            .collect();
+        let collected = valid_key_hashes.len();
+        let (satisfied, remaining) = signature_progress(access_rule, &valid_key_hashes);
 
         Ok(SignatureStatus {
             proposal_id,
@@ -314,22 +560,30 @@ impl SignatureCollector {
                 .map(|s| SignatureSummary {
                     signer_public_key: s.signer_public_key,
                     signer_key_hash: s.signer_key_hash,
+                    signer_key_type: s.signer_key_type,
                     created_at: s.created_at,
+                    is_valid: s.is_valid,
                 })
                 .collect(),
             threshold: access_rule.threshold,
             collected,
+            satisfied,
             remaining,
             signers,
+            outcomes: Vec::new(),
         })
     }
 
     /// Get raw signature data for transaction reconstruction.
     ///
-    /// Returns (public_key_hex, signature_bytes) pairs for all signatures on a proposal.
-    pub async fn get_raw_signatures(&self, proposal_id: Uuid) -> Result<Vec<(String, Vec<u8>)>> {
-        let rows: Vec<(String, Vec<u8>)> = sqlx::query_as(
-            "SELECT signer_public_key, signature_bytes FROM signatures WHERE proposal_id = $1 ORDER BY created_at ASC",
+    /// Returns (key_hash, public_key_hex, signature_bytes, key_type) tuples for
+    /// the valid signatures on a proposal, oldest first.
+    pub async fn get_raw_signatures(
+        &self,
+        proposal_id: Uuid,
+    ) -> Result<Vec<(String, String, Vec<u8>, String)>> {
+        let rows: Vec<(String, String, Vec<u8>, String)> = sqlx::query_as(
+            "SELECT signer_key_hash, signer_public_key, signature_bytes, signer_key_type FROM signatures WHERE proposal_id = $1 AND is_valid ORDER BY created_at ASC",
         )
         .bind(proposal_id)
         .fetch_all(&self.pool)
@@ -341,7 +595,7 @@ impl SignatureCollector {
     async fn list_signatures(&self, proposal_id: Uuid) -> Result<Vec<Signature>> {
         let rows = sqlx::query_as::<_, Signature>(
             r#"
-            SELECT id, proposal_id, signer_public_key, signer_key_hash, signature_bytes,
+            SELECT id, proposal_id, signer_public_key, signer_key_hash, signer_key_type, signature_bytes,
                    signed_partial_transaction_hex, created_at, is_valid
             FROM signatures
             WHERE proposal_id = $1
@@ -355,12 +609,18 @@ impl SignatureCollector {
         Ok(rows)
     }
 
-    async fn count_signatures(&self, proposal_id: Uuid) -> Result<i64> {
-        let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM signatures WHERE proposal_id = $1")
-            .bind(proposal_id)
-            .fetch_one(&self.pool)
-            .await?;
-        Ok(row.0)
+    async fn signed_key_hashes(
+        &self,
+        conn: &mut PgConnection,
+        proposal_id: Uuid,
+    ) -> Result<Vec<String>> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT signer_key_hash FROM signatures WHERE proposal_id = $1 AND is_valid",
+        )
+        .bind(proposal_id)
+        .fetch_all(conn)
+        .await?;
+        Ok(rows.into_iter().map(|(h,)| h).collect())
     }
 }
 
@@ -373,7 +633,12 @@ mod tests {
         signer_private_key: &Ed25519PrivateKey,
     ) -> (String, Ed25519PublicKey) {
         let public_key = signer_private_key.public_key();
+        let hex_str = build_test_signed_partial_with(signer_private_key);
+        (hex_str, public_key)
+    }
 
+    /// Build a signed partial transaction hex signed by any key type.
+    fn build_test_signed_partial_with(signer: &impl radix_transactions::signing::Signer) -> String {
         let manifest_text = r#"CALL_METHOD
     Address("account_tdx_2_1cx3u3xgr9anc9fk54dxzsz6k2n6lnadludkx4mx5re5erl8jt9lpnp")
     "withdraw"
@@ -411,13 +676,11 @@ YIELD_TO_PARENT;
                 max_proposer_timestamp_exclusive: None,
             })
             .manifest(manifest)
-            .sign(signer_private_key)
+            .sign(signer)
             .build();
 
         let raw = detailed.partial_transaction.to_raw().unwrap();
-        let hex_str = hex::encode(raw.as_slice());
-
-        (hex_str, public_key)
+        hex::encode(raw.as_slice())
     }
 
     #[test]
@@ -436,6 +699,13 @@ YIELD_TO_PARENT;
         assert_eq!(a, b);
     }
 
+    #[test]
+    fn compute_secp256k1_key_hash_matches_sdk() {
+        let public_key = Secp256k1PrivateKey::from_u64(5).unwrap().public_key();
+        let hash = compute_key_hash(&hex::encode(public_key.0)).unwrap();
+        assert_eq!(hash, hex::encode(public_key.get_hash().get_hash_bytes()));
+    }
+
     #[test]
     fn compute_key_hash_rejects_wrong_length() {
         let short_hex = hex::encode([1u8; 16]);
@@ -444,28 +714,27 @@ YIELD_TO_PARENT;
 
     #[test]
     fn find_signer_by_hash_finds_match() {
-        let access_rule = AccessRuleInfo {
-            signers: vec![SignerInfo {
+        let access_rule = AccessRuleInfo::count_of(
+            vec![SignerInfo {
                 key_hash: "aabbccdd".into(),
                 key_type: "EddsaEd25519".into(),
                 badge_resource: "resource_test".into(),
                 badge_local_id: "[aabbccdd]".into(),
             }],
-            threshold: 1,
-            is_updatable: true,
-        };
+            1,
+        );
         assert!(find_signer_by_hash(&access_rule, "aabbccdd").is_some());
         assert!(find_signer_by_hash(&access_rule, "00000000").is_none());
     }
 
     #[test]
     fn extract_signature_rejects_invalid_hex() {
-        assert!(extract_signature_from_hex("not-hex!").is_err());
+        assert!(extract_signatures_from_hex("not-hex!").is_err());
     }
 
     #[test]
     fn extract_signature_rejects_invalid_payload() {
-        assert!(extract_signature_from_hex("deadbeef").is_err());
+        assert!(extract_signatures_from_hex("deadbeef").is_err());
     }
 
     #[test]
@@ -473,7 +742,7 @@ YIELD_TO_PARENT;
         let private_key = Ed25519PrivateKey::from_u64(1).unwrap();
         let (hex_str, expected_pk) = build_test_signed_partial(&private_key);
 
-        let (sig, pk_hex) = extract_signature_from_hex(&hex_str).unwrap();
+        let (sig, pk_hex) = extract_signatures_from_hex(&hex_str).unwrap().remove(0);
 
         // Verify we got an Ed25519 signature
         assert!(matches!(sig, SignatureWithPublicKeyV1::Ed25519 { .. }));
@@ -482,12 +751,78 @@ YIELD_TO_PARENT;
         assert_eq!(pk_hex, hex::encode(expected_pk.0));
     }
 
+    #[test]
+    fn extract_secp256k1_signature_recovers_public_key() {
+        let private_key = Secp256k1PrivateKey::from_u64(3).unwrap();
+        let hex_str = build_test_signed_partial_with(&private_key);
+
+        let (sig, pk_hex) = extract_signatures_from_hex(&hex_str).unwrap().remove(0);
+
+        assert!(matches!(sig, SignatureWithPublicKeyV1::Secp256k1 { .. }));
+        assert_eq!(signature_key_type(&sig), "EcdsaSecp256k1");
+        assert_eq!(pk_hex, hex::encode(private_key.public_key().0));
+
+        let expected_hash = hex::encode(private_key.public_key().get_hash().get_hash_bytes());
+        assert_eq!(compute_key_hash(&pk_hex).unwrap(), expected_hash);
+    }
+
+    #[test]
+    fn extract_signatures_returns_every_root_signature() {
+        let ed_key = Ed25519PrivateKey::from_u64(1).unwrap();
+        let secp_key = Secp256k1PrivateKey::from_u64(2).unwrap();
+        let (hex_str, ed_pk) = build_test_signed_partial(&ed_key);
+
+        // Co-sign the same subintent with a second key, as a co-signing tool would
+        let raw = RawSignedPartialTransaction::from_vec(hex::decode(&hex_str).unwrap());
+        let mut signed_partial = SignedPartialTransactionV2::from_raw(&raw).unwrap();
+        let subintent_hash = signed_partial
+            .prepare(PreparationSettings::latest_ref())
+            .unwrap()
+            .subintent_hash();
+        signed_partial
+            .root_subintent_signatures
+            .signatures
+            .push(IntentSignatureV1(
+                radix_transactions::signing::Signer::sign_with_public_key(
+                    &secp_key,
+                    &subintent_hash,
+                ),
+            ));
+        let cosigned_hex = hex::encode(signed_partial.to_raw().unwrap().as_slice());
+
+        let extracted = extract_signatures_from_hex(&cosigned_hex).unwrap();
+
+        assert_eq!(extracted.len(), 2);
+        assert_eq!(extracted[0].1, hex::encode(ed_pk.0));
+        assert_eq!(signature_key_type(&extracted[1].0), "EcdsaSecp256k1");
+        assert_eq!(extracted[1].1, hex::encode(secp_key.public_key().0));
+    }
+
+    #[test]
+    fn extract_signature_rejects_signature_over_other_hash() {
+        let private_key = Ed25519PrivateKey::from_u64(1).unwrap();
+        let (hex_str, _) = build_test_signed_partial(&private_key);
+
+        // Swap the valid signature for one made over an unrelated hash
+        let raw = RawSignedPartialTransaction::from_vec(hex::decode(&hex_str).unwrap());
+        let mut signed_partial = SignedPartialTransactionV2::from_raw(&raw).unwrap();
+        let forged = radix_transactions::signing::Signer::sign_with_public_key(
+            &private_key,
+            &hash("not the subintent"),
+        );
+        signed_partial.root_subintent_signatures.signatures = vec![IntentSignatureV1(forged)];
+        let forged_hex = hex::encode(signed_partial.to_raw().unwrap().as_slice());
+
+        let err = extract_signatures_from_hex(&forged_hex).unwrap_err();
+        assert!(err.to_string().starts_with("Invalid signature"), "{err}");
+    }
+
     #[test]
     fn extracted_public_key_hash_matches_computed() {
         let private_key = Ed25519PrivateKey::from_u64(42).unwrap();
         let (hex_str, expected_pk) = build_test_signed_partial(&private_key);
 
-        let (_sig, pk_hex) = extract_signature_from_hex(&hex_str).unwrap();
+        let (_sig, pk_hex) = extract_signatures_from_hex(&hex_str).unwrap().remove(0);
         let hash = compute_key_hash(&pk_hex).unwrap();
 
         // Verify hash matches what Radix SDK computes
@@ -496,25 +831,60 @@ YIELD_TO_PARENT;
         assert_eq!(hash, expected_hash_hex);
     }
 
+    fn test_signer(key_hash: &str) -> SignerInfo {
+        SignerInfo {
+            key_hash: key_hash.into(),
+            key_type: "EddsaEd25519".into(),
+            badge_resource: "resource_test".into(),
+            badge_local_id: format!("[{key_hash}]"),
+        }
+    }
+
+    #[test]
+    fn signature_progress_follows_nested_rule() {
+        // "2 of council AND 1 of auditors"
+        let council = AccessRuleInfo::count_of(["c1", "c2", "c3"].map(test_signer).to_vec(), 2);
+        let auditors = AccessRuleInfo::count_of(["a1", "a2"].map(test_signer).to_vec(), 1);
+        let access_rule = AccessRuleInfo {
+            signers: [council.signers, auditors.signers].concat(),
+            threshold: 3,
+            is_updatable: true,
+            rule: crate::gateway::AccessRuleNode::AllOf {
+                access_rules: vec![council.rule, auditors.rule],
+            },
+        };
+
+        assert_eq!(signature_progress(&access_rule, &[]), (false, 3));
+        // Three council signatures reach the flat threshold but not the rule
+        assert_eq!(
+            signature_progress(&access_rule, &["c1", "c2", "c3"]),
+            (false, 1)
+        );
+        assert_eq!(signature_progress(&access_rule, &["a1", "a2"]), (false, 2));
+        assert_eq!(
+            signature_progress(&access_rule, &["c1", "c3", "a2"]),
+            (true, 0)
+        );
+    }
+
     #[test]
     fn signer_validation_accepts_valid_signer() {
         let private_key = Ed25519PrivateKey::from_u64(7).unwrap();
         let public_key = private_key.public_key();
         let key_hash = hex::encode(public_key.get_hash().get_hash_bytes());
 
-        let access_rule = AccessRuleInfo {
-            signers: vec![SignerInfo {
+        let access_rule = AccessRuleInfo::count_of(
+            vec![SignerInfo {
                 key_hash: key_hash.clone(),
                 key_type: "EddsaEd25519".into(),
                 badge_resource: "resource_test".into(),
                 badge_local_id: format!("[{key_hash}]"),
             }],
-            threshold: 1,
-            is_updatable: true,
-        };
+            1,
+        );
 
         let (hex_str, _) = build_test_signed_partial(&private_key);
-        let (_sig, pk_hex) = extract_signature_from_hex(&hex_str).unwrap();
+        let (_sig, pk_hex) = extract_signatures_from_hex(&hex_str).unwrap().remove(0);
         let computed_hash = compute_key_hash(&pk_hex).unwrap();
 
         assert!(find_signer_by_hash(&access_rule, &computed_hash).is_some());
@@ -525,19 +895,18 @@ YIELD_TO_PARENT;
         let private_key = Ed25519PrivateKey::from_u64(99).unwrap();
 
         // Access rule has a different signer
-        let access_rule = AccessRuleInfo {
-            signers: vec![SignerInfo {
+        let access_rule = AccessRuleInfo::count_of(
+            vec![SignerInfo {
                 key_hash: "0000000000000000000000000000000000000000000000000000000000".into(),
                 key_type: "EddsaEd25519".into(),
                 badge_resource: "resource_test".into(),
                 badge_local_id: "[0000...]".into(),
             }],
-            threshold: 1,
-            is_updatable: true,
-        };
+            1,
+        );
 
         let (hex_str, _) = build_test_signed_partial(&private_key);
-        let (_sig, pk_hex) = extract_signature_from_hex(&hex_str).unwrap();
+        let (_sig, pk_hex) = extract_signatures_from_hex(&hex_str).unwrap().remove(0);
         let computed_hash = compute_key_hash(&pk_hex).unwrap();
 
         assert!(find_signer_by_hash(&access_rule, &computed_hash).is_none());
@@ -636,4 +1005,143 @@ YIELD_TO_PARENT;
             "Different discriminators should produce different subintent hashes"
         );
     }
+
+    #[test]
+    fn cancellation_signature_verifies_for_both_key_types() {
+        let proposal_id = Uuid::new_v4();
+        let message = cancellation_message_hash(proposal_id, "subtxid_tdx_2_1test");
+
+        let ed_key = Ed25519PrivateKey::from_u64(7).unwrap();
+        let ed_pk_hex = hex::encode(ed_key.public_key().0);
+        let key_hash =
+            verify_message_signature(&message, &ed_pk_hex, &hex::encode(ed_key.sign(message).0))
+                .unwrap();
+        assert_eq!(key_hash, compute_key_hash(&ed_pk_hex).unwrap());
+
+        let secp_key = Secp256k1PrivateKey::from_u64(7).unwrap();
+        let secp_pk_hex = hex::encode(secp_key.public_key().0);
+        let key_hash = verify_message_signature(
+            &message,
+            &secp_pk_hex,
+            &hex::encode(secp_key.sign(&message).0),
+        )
+        .unwrap();
+        assert_eq!(key_hash, compute_key_hash(&secp_pk_hex).unwrap());
+    }
+
+    #[test]
+    fn cancellation_signature_for_another_proposal_is_rejected() {
+        let key = Ed25519PrivateKey::from_u64(7).unwrap();
+        let signed_for = cancellation_message_hash(Uuid::new_v4(), "subtxid_tdx_2_1test");
+        let presented_for = cancellation_message_hash(Uuid::new_v4(), "subtxid_tdx_2_1test");
+
+        let err = verify_message_signature(
+            &presented_for,
+            &hex::encode(key.public_key().0),
+            &hex::encode(key.sign(signed_for).0),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Invalid signature"));
+    }
+
+    /// Fires one signature per signer at the same proposal concurrently.
+    /// Needs a Postgres database: `DATABASE_URL=... cargo test -- --ignored`.
+    #[test]
+    #[ignore]
+    fn integration_concurrent_signatures_reach_ready_once() {
+        const SIGNERS: u64 = 12;
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        rt.block_on(async {
+            let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+            let pool = PgPool::connect(&database_url).await.unwrap();
+            sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+            let keys: Vec<Ed25519PrivateKey> = (1..=SIGNERS)
+                .map(|i| Ed25519PrivateKey::from_u64(1000 + i).unwrap())
+                .collect();
+            let signers = keys
+                .iter()
+                .map(|key| {
+                    let key_hash = hex::encode(key.public_key().get_hash().get_hash_bytes());
+                    SignerInfo {
+                        key_hash: key_hash.clone(),
+                        key_type: "EddsaEd25519".into(),
+                        badge_resource: "resource_test".into(),
+                        badge_local_id: format!("[{key_hash}]"),
+                    }
+                })
+                .collect();
+            // Only the last signature can make the proposal Ready
+            let access_rule = Arc::new(AccessRuleInfo::count_of(signers, SIGNERS as u8));
+
+            let signed_partials: Vec<String> =
+                keys.iter().map(build_test_signed_partial_with).collect();
+            let subintent_hash =
+                compute_subintent_hash_from_signed_partial_hex(&signed_partials[0], 0x02).unwrap();
+
+            let proposal_store = Arc::new(ProposalStore::new(pool.clone()));
+            let proposal = proposal_store
+                .create(crate::proposal_store::CreateProposal {
+                    manifest_text: "YIELD_TO_PARENT;".into(),
+                    multisig_account: "account_tdx_2_test".into(),
+                    epoch_min: 1000,
+                    epoch_max: 1100,
+                    subintent_hash: subintent_hash.clone(),
+                    intent_discriminator: 12345,
+                    min_proposer_timestamp: 0,
+                    max_proposer_timestamp: 0,
+                    partial_transaction_bytes: Vec::new(),
+                    proposer_key_hash: None,
+                    auto_submit: false,
+                    preview: None,
+                    fee_mode: Default::default(),
+                    reimbursed_fee_payer: None,
+                    fee_reimbursement: None,
+                })
+                .await
+                .unwrap();
+
+            let (queue, _jobs) = crate::submission_worker::queue();
+            let collector = Arc::new(SignatureCollector::new(pool.clone(), queue));
+
+            let tasks: Vec<_> = signed_partials
+                .into_iter()
+                .map(|signed_partial| {
+                    let collector = collector.clone();
+                    let proposal_store = proposal_store.clone();
+                    let access_rule = access_rule.clone();
+                    let subintent_hash = subintent_hash.clone();
+                    tokio::spawn(async move {
+                        collector
+                            .add_signature(
+                                proposal.id,
+                                &signed_partial,
+                                &access_rule,
+                                &proposal_store,
+                                &subintent_hash,
+                                0x02,
+                            )
+                            .await
+                    })
+                })
+                .collect();
+            for task in tasks {
+                let status = task.await.unwrap().unwrap();
+                assert!(status
+                    .outcomes
+                    .iter()
+                    .all(|o| o.outcome == SignatureOutcomeKind::Accepted));
+            }
+
+            let proposal = proposal_store.get(proposal.id).await.unwrap().unwrap();
+            assert_eq!(proposal.status, ProposalStatus::Ready);
+            let status = collector
+                .get_signature_status(proposal.id, &access_rule)
+                .await
+                .unwrap();
+            assert_eq!(status.collected, SIGNERS as usize);
+            assert_eq!(status.remaining, 0);
+        });
+    }
 }
@@ -0,0 +1,1021 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use radix_common::math::Decimal;
+use radix_transactions::model::SignedPartialTransactionV2;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::fee_payer::{FeePayer, FeePayerBalance, FeePayerPool};
+use crate::gateway::{
+    CommitOutcome, FailureReason, GatewayClient, PreviewOutcome, PreviewStatus,
+    TransactionStatusResponse,
+};
+use crate::notary_signer::NotarySigner;
+use crate::proposal_store::{
+    FeeMode, FeeReservation, ProposalStatus, ProposalStore, SubmissionAttempt,
+};
+use crate::signature_collector::SignatureCollector;
+use crate::transaction_builder::{self, FeeLock, MainTransactionSigners, StoredSignature};
+
+/// Status polls (2s apart) per pass over a submitted transaction. One still
+/// pending after them is left to [`recover_stuck_submissions`].
+const MAX_COMMIT_POLLS: u32 = 60;
+
+enum Job {
+    /// Drive a persisted submission attempt to completion.
+    Attempt(Uuid),
+    /// Compose and submit a proposal that became Ready with `auto_submit` set.
+    AutoSubmit(Uuid),
+}
+
+/// Handle for handing work to the background submission worker.
+///
+/// Attempts are persisted before they are enqueued, so the queue itself only
+/// carries ids; anything lost with the process is picked up again by
+/// [`recover_stuck_submissions`].
+#[derive(Clone)]
+pub struct SubmissionQueue {
+    sender: mpsc::UnboundedSender<Job>,
+    /// Attempts enqueued or being processed, so recovery never drives the
+    /// same attempt twice.
+    active: Arc<Mutex<HashSet<Uuid>>>,
+}
+
+/// Receiving end of a [`SubmissionQueue`], consumed by [`start`].
+pub struct SubmissionJobs(mpsc::UnboundedReceiver<Job>);
+
+#[cfg(test)]
+impl SubmissionJobs {
+    /// The proposal of the next queued job, if it is an auto-submit.
+    pub fn try_recv_auto_submit(&mut self) -> Option<Uuid> {
+        match self.0.try_recv() {
+            Ok(Job::AutoSubmit(proposal_id)) => Some(proposal_id),
+            _ => None,
+        }
+    }
+}
+
+/// Create the queue up front so components built before the worker can enqueue.
+pub fn queue() -> (SubmissionQueue, SubmissionJobs) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let queue = SubmissionQueue {
+        sender,
+        active: Arc::new(Mutex::new(HashSet::new())),
+    };
+    (queue, SubmissionJobs(receiver))
+}
+
+impl SubmissionQueue {
+    /// Enqueue an attempt, unless it is already queued or being processed.
+    pub fn enqueue(&self, attempt_id: Uuid) -> Result<()> {
+        if !self.lock_active().insert(attempt_id) {
+            return Ok(());
+        }
+        self.send(Job::Attempt(attempt_id)).inspect_err(|_| {
+            self.lock_active().remove(&attempt_id);
+        })
+    }
+
+    fn is_active(&self, attempt_id: Uuid) -> bool {
+        self.lock_active().contains(&attempt_id)
+    }
+
+    fn finish(&self, attempt_id: Uuid) {
+        self.lock_active().remove(&attempt_id);
+    }
+
+    fn lock_active(&self) -> std::sync::MutexGuard<'_, HashSet<Uuid>> {
+        self.active.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn enqueue_auto_submit(&self, proposal_id: Uuid) -> Result<()> {
+        self.send(Job::AutoSubmit(proposal_id))
+    }
+
+    /// Hand a proposal that just became Ready to auto-submission, if it opted in.
+    pub fn proposal_ready(&self, proposal_id: Uuid, auto_submit: bool) -> Result<()> {
+        if auto_submit {
+            self.enqueue_auto_submit(proposal_id)?;
+        }
+        Ok(())
+    }
+
+    fn send(&self, job: Job) -> Result<()> {
+        self.sender
+            .send(job)
+            .map_err(|_| anyhow!("Submission worker is not running"))
+    }
+}
+
+/// Everything needed to compose a proposal's main transaction and submit it.
+pub struct Submitter {
+    pub proposal_store: Arc<ProposalStore>,
+    pub signature_collector: Arc<SignatureCollector>,
+    pub gateway: Arc<GatewayClient>,
+    pub network_id: u8,
+    pub fee_payers: Arc<FeePayerPool>,
+    /// Dedicated notary key. When set, fee payers only sign to authorise their
+    /// `lock_fee`; otherwise each fee payer key also notarizes.
+    pub notary: Option<Arc<dyn NotarySigner>>,
+    pub fee_lock: FeeLockPolicy,
+    pub queue: SubmissionQueue,
+}
+
+/// How much XRD the main intent locks for fees, sized from a preview.
+#[derive(Debug, Clone, Copy)]
+pub struct FeeLockPolicy {
+    /// Added on top of the estimated fee, as a fraction (0.2 = 20%).
+    pub margin: Decimal,
+    /// The most locked for one transaction. Previews lock exactly this much.
+    pub max: Decimal,
+}
+
+impl FeeLockPolicy {
+    /// The fee to lock for a transaction whose preview estimated `estimate`.
+    pub fn lock_amount(&self, estimate: Decimal) -> Result<Decimal> {
+        if estimate > self.max {
+            return Err(anyhow!(
+                "Estimated fee of {estimate} XRD exceeds the fee lock cap of {} XRD",
+                self.max
+            ));
+        }
+        let with_margin = estimate * (Decimal::ONE + self.margin);
+        Ok(if with_margin > self.max {
+            self.max
+        } else {
+            with_margin
+        })
+    }
+}
+
+/// A submission persisted and handed to the worker.
+pub struct StartedSubmission {
+    pub attempt_id: Uuid,
+    pub tx_hash: String,
+}
+
+impl Submitter {
+    /// Keys for a main transaction whose fee `fee_payer` locks, or which has
+    /// no fee lock when `fee_payer` is `None`.
+    fn signers<'a>(&'a self, fee_payer: Option<&'a FeePayer>) -> MainTransactionSigners<'a> {
+        match (&self.notary, fee_payer) {
+            (Some(notary), fee_payer) => MainTransactionSigners {
+                notary: notary.as_ref(),
+                fee_payer: fee_payer.map(|payer| payer.signer.as_ref()),
+            },
+            (None, Some(fee_payer)) => MainTransactionSigners {
+                notary: fee_payer.signer.as_ref(),
+                fee_payer: None,
+            },
+            // Nothing to authorise, so any fee payer key can notarize
+            (None, None) => MainTransactionSigners {
+                notary: self.fee_payers.any().signer.as_ref(),
+                fee_payer: None,
+            },
+        }
+    }
+
+    /// The fee payer for a proposal's main transaction; `None` when the
+    /// subintent pays its own fee. A reimbursing subintent pays back one
+    /// specific fee payer, which must then be the one to pay.
+    async fn select_fee_payer(
+        &self,
+        fee_mode: FeeMode,
+        reimbursed_fee_payer: Option<&str>,
+    ) -> Result<Option<(&FeePayer, FeePayerBalance)>> {
+        let selected = match fee_mode {
+            FeeMode::FeePayer => self.fee_payers.select().await,
+            FeeMode::Multisig => return Ok(None),
+            FeeMode::ReimburseFeePayer => {
+                let account = reimbursed_fee_payer
+                    .ok_or_else(|| anyhow!("Reimbursing proposal has no fee payer"))?;
+                self.fee_payers.select_account(account).await
+            }
+        };
+        selected.map(Some).context("Failed to select a fee payer")
+    }
+
+    /// Simulate a proposal's unsigned subintent, wrapped in the same main
+    /// transaction the fee payer would submit.
+    pub async fn preview_subintent(
+        &self,
+        partial_transaction_bytes: &[u8],
+        fee_mode: FeeMode,
+        reimbursed_fee_payer: Option<&str>,
+    ) -> Result<PreviewOutcome> {
+        let fee_payer = self
+            .select_fee_payer(fee_mode, reimbursed_fee_payer)
+            .await?;
+        let unsigned =
+            transaction_builder::reconstruct_signed_partial(partial_transaction_bytes, &[])
+                .context("Failed to reconstruct subintent")?;
+
+        let current_epoch = self
+            .gateway
+            .get_current_epoch()
+            .await
+            .context("Failed to get current epoch")?;
+
+        self.preview_main_transaction(fee_payer.map(|(payer, _)| payer), current_epoch, unsigned)
+            .await
+    }
+
+    /// Size the reimbursement for a subintent that does not yet pay one back:
+    /// preview it paid by the pool's best fee payer, and return that payer's
+    /// account with the fee lock the preview implies.
+    pub async fn size_fee_reimbursement(
+        &self,
+        partial_transaction_bytes: &[u8],
+    ) -> Result<(String, Decimal)> {
+        let (fee_payer, _) = self
+            .fee_payers
+            .select()
+            .await
+            .context("Failed to select a fee payer")?;
+        let preview = self
+            .preview_subintent(
+                partial_transaction_bytes,
+                FeeMode::ReimburseFeePayer,
+                Some(&fee_payer.account),
+            )
+            .await?;
+        if preview.status != PreviewStatus::Succeeded {
+            return Err(anyhow!(
+                "Preview did not succeed: {}",
+                preview
+                    .error_message
+                    .as_deref()
+                    .unwrap_or("no error message")
+            ));
+        }
+        let fee_estimate = preview
+            .fee_estimate
+            .ok_or_else(|| anyhow!("Preview has no fee summary"))?;
+
+        Ok((
+            fee_payer.account.clone(),
+            self.fee_lock.lock_amount(fee_estimate)?,
+        ))
+    }
+
+    /// Preview the main transaction wrapping `subintent`, locking the maximum
+    /// fee from `fee_payer` (or nothing, if the subintent pays).
+    async fn preview_main_transaction(
+        &self,
+        fee_payer: Option<&FeePayer>,
+        current_epoch: u64,
+        subintent: SignedPartialTransactionV2,
+    ) -> Result<PreviewOutcome> {
+        let preview_hex = transaction_builder::build_preview_transaction_hex(
+            self.network_id,
+            current_epoch,
+            self.signers(fee_payer),
+            fee_payer.map(|payer| FeeLock {
+                account: payer.component_address(),
+                amount: self.fee_lock.max,
+            }),
+            subintent,
+        )
+        .context("Failed to build preview transaction")?;
+
+        self.gateway.preview_transaction_v2(&preview_hex).await
+    }
+
+    /// Reconstruct a proposal's subintent with a minimal set of its valid
+    /// signatures attached, checking each signature before it is used.
+    pub async fn signed_partial(&self, proposal_id: Uuid) -> Result<SignedPartialTransactionV2> {
+        let proposal = self
+            .proposal_store
+            .get(proposal_id)
+            .await
+            .context("Failed to get proposal")?
+            .ok_or_else(|| anyhow!("Proposal {proposal_id} not found"))?;
+
+        // Reconstruct the DAO withdrawal signed partial from stored data
+        let partial_bytes = self
+            .proposal_store
+            .get_partial_transaction_bytes(proposal_id)
+            .await
+            .context("Failed to get partial transaction bytes")?;
+
+        let raw_sigs = self
+            .signature_collector
+            .get_raw_signatures(proposal_id)
+            .await
+            .context("Failed to get signatures")?;
+
+        // Attach only the signatures the current access rule needs
+        let access_rule = self
+            .gateway
+            .read_access_rule(&proposal.multisig_account)
+            .await
+            .context("Failed to read access rule")?;
+        let key_hashes: Vec<&str> = raw_sigs.iter().map(|(hash, ..)| hash.as_str()).collect();
+        let selected = access_rule.minimal_signers(&key_hashes).ok_or_else(|| {
+            anyhow!("Valid signatures on proposal {proposal_id} no longer satisfy the access rule")
+        })?;
+        let selected: Vec<String> = selected.into_iter().map(str::to_string).collect();
+
+        let stored_sigs: Vec<StoredSignature> = raw_sigs
+            .into_iter()
+            .filter(|(hash, ..)| selected.contains(hash))
+            .map(|(_, pk, sig, key_type)| StoredSignature {
+                public_key_hex: pk,
+                signature_bytes: sig,
+                key_type,
+            })
+            .collect();
+
+        let signed_partial =
+            transaction_builder::reconstruct_signed_partial(&partial_bytes, &stored_sigs)
+                .context("Failed to reconstruct signed partial")?;
+
+        // Re-check every stored signature before spending fees on submission
+        transaction_builder::verify_root_signatures(&signed_partial)
+            .context("Signature verification failed")?;
+
+        Ok(signed_partial)
+    }
+
+    /// Compose the main transaction for a Ready proposal, persist it as a new
+    /// attempt (moving the proposal to Submitting) and enqueue it.
+    ///
+    /// Unless the proposal's subintent locks its own fee, the fee is paid by
+    /// the pool's fee payer with the most available XRD, locking the fee
+    /// estimated by previewing the transaction plus the configured margin.
+    pub async fn submit(&self, proposal_id: Uuid) -> Result<StartedSubmission> {
+        let proposal = self
+            .proposal_store
+            .get(proposal_id)
+            .await
+            .context("Failed to get proposal")?
+            .ok_or_else(|| anyhow!("Proposal {proposal_id} not found"))?;
+        if proposal.externally_submitted_at.is_some() {
+            return Err(anyhow!(
+                "Proposal {proposal_id} was exported for external submission"
+            ));
+        }
+
+        let withdrawal_signed_partial = self.signed_partial(proposal_id).await?;
+
+        // Get current epoch for the main transaction
+        let current_epoch = self
+            .gateway
+            .get_current_epoch()
+            .await
+            .context("Failed to get current epoch")?;
+
+        let fee_payer = self
+            .select_fee_payer(proposal.fee_mode, proposal.reimbursed_fee_payer.as_deref())
+            .await?;
+
+        // Size the fee lock from a preview of the transaction about to be sent
+        let preview = self
+            .preview_main_transaction(
+                fee_payer.as_ref().map(|(payer, _)| *payer),
+                current_epoch,
+                withdrawal_signed_partial.clone(),
+            )
+            .await
+            .context("Failed to preview main transaction")?;
+        if preview.status != PreviewStatus::Succeeded {
+            return Err(anyhow!(
+                "Preview of the main transaction did not succeed: {}",
+                preview
+                    .error_message
+                    .as_deref()
+                    .unwrap_or("no error message")
+            ));
+        }
+        let fee_estimate = preview
+            .fee_estimate
+            .ok_or_else(|| anyhow!("Preview of the main transaction has no fee summary"))?;
+        let fee_lock = match &fee_payer {
+            Some((fee_payer, fee_payer_balance)) => {
+                let amount = self.fee_lock.lock_amount(fee_estimate)?;
+                if fee_payer_balance.available() < amount {
+                    return Err(anyhow!(
+                        "Fee payer {} cannot cover a fee lock of {amount} XRD ({} XRD available)",
+                        fee_payer.account,
+                        fee_payer_balance.available()
+                    ));
+                }
+                Some(FeeLock {
+                    account: fee_payer.component_address(),
+                    amount,
+                })
+            }
+            None => None,
+        };
+        // Checked again when persisting, against reservations made since
+        let fee_reservation = fee_payer
+            .as_ref()
+            .zip(fee_lock)
+            .map(|((payer, balance), lock)| FeeReservation {
+                account: payer.account.as_str(),
+                amount: lock.amount,
+                balance: balance.balance,
+            });
+        let fee_payer = fee_payer.as_ref().map(|(payer, _)| *payer);
+
+        let composed = transaction_builder::compose_main_transaction(
+            self.network_id,
+            current_epoch,
+            self.signers(fee_payer),
+            fee_lock,
+            withdrawal_signed_partial,
+        )
+        .await
+        .context("Failed to compose main transaction")?;
+
+        // Persist the transaction and move Ready → Submitting in one step
+        let attempt_id = self
+            .proposal_store
+            .start_submission(
+                proposal_id,
+                fee_reservation,
+                &composed,
+                &fee_estimate.to_string(),
+            )
+            .await
+            .context("Failed to start submission")?;
+
+        // The attempt is durable, so if the worker is gone it is resumed on restart
+        if let Err(e) = self.queue.enqueue(attempt_id) {
+            tracing::error!("Failed to enqueue submission attempt {attempt_id}: {e}");
+        }
+
+        Ok(StartedSubmission {
+            attempt_id,
+            tx_hash: composed.intent_hash,
+        })
+    }
+}
+
+/// Start the submission worker, resuming work left unfinished by a previous run:
+/// in-flight attempts, and auto-submit proposals that became Ready but were
+/// never submitted.
+pub async fn start(submitter: Arc<Submitter>, jobs: SubmissionJobs) -> Result<()> {
+    recover_stuck_submissions(&submitter).await?;
+
+    for proposal in submitter.proposal_store.list_active().await? {
+        if proposal.status == ProposalStatus::Ready && proposal.auto_submit {
+            submitter.queue.enqueue_auto_submit(proposal.id)?;
+        }
+    }
+
+    let SubmissionJobs(mut receiver) = jobs;
+    tokio::spawn(async move {
+        while let Some(job) = receiver.recv().await {
+            let submitter = submitter.clone();
+            tokio::spawn(async move {
+                match job {
+                    Job::Attempt(attempt_id) => {
+                        if let Err(e) = process_attempt(
+                            &submitter.proposal_store,
+                            &submitter.gateway,
+                            attempt_id,
+                        )
+                        .await
+                        {
+                            tracing::error!("Submission attempt {attempt_id} errored: {e}");
+                        }
+                        submitter.queue.finish(attempt_id);
+                    }
+                    Job::AutoSubmit(proposal_id) => auto_submit(&submitter, proposal_id).await,
+                }
+            });
+        }
+    });
+
+    Ok(())
+}
+
+/// What recovery does with a proposal left in Submitting.
+#[derive(Debug, PartialEq, Eq)]
+enum Recovery {
+    /// No transaction was ever persisted: fail the proposal so it can be retried.
+    FailUnpersisted,
+    /// The latest attempt already finished: move the proposal to match it.
+    Settle(ProposalStatus),
+    /// Submit the persisted transaction.
+    Submit,
+    /// The Gateway never saw the submitted transaction: submit it again.
+    Resubmit,
+    /// Resume polling until the Gateway reports a final status.
+    Poll,
+    /// The transaction's epoch window passed without it committing.
+    Expire,
+}
+
+/// What the Gateway reports for a submitted attempt's transaction.
+struct LedgerCheck<'a> {
+    status: &'a TransactionStatusResponse,
+    /// The current epoch is past the transaction's epoch window.
+    window_passed: bool,
+}
+
+/// Decide how to recover from the latest attempt's status (`None` if there
+/// is no attempt) and, for a submitted attempt, the Gateway's view of it.
+fn decide_recovery(attempt_status: Option<&str>, ledger: Option<LedgerCheck<'_>>) -> Recovery {
+    match attempt_status {
+        None => Recovery::FailUnpersisted,
+        Some("committed") => Recovery::Settle(ProposalStatus::Committed),
+        Some("failed") => Recovery::Settle(ProposalStatus::Failed),
+        Some("submitted") => {
+            let Some(LedgerCheck {
+                status,
+                window_passed,
+            }) = ledger
+            else {
+                return Recovery::Poll;
+            };
+            // A final status is recorded by the first poll
+            if !matches!(status.status.as_str(), "Pending" | "Unknown") {
+                Recovery::Poll
+            } else if window_passed {
+                Recovery::Expire
+            } else if status.status == "Unknown" && status.known_payloads.is_empty() {
+                Recovery::Resubmit
+            } else {
+                Recovery::Poll
+            }
+        }
+        Some(_) => Recovery::Submit,
+    }
+}
+
+/// Resolve proposals left in Submitting by a crash, a dropped job, or a
+/// transaction that stayed pending for longer than one pass of polling.
+///
+/// The latest attempt of each such proposal decides what happens (see
+/// [`decide_recovery`]):
+/// - `committed`/`failed`: the proposal is moved to match it;
+/// - `pending`: the persisted transaction is (re-)submitted;
+/// - `submitted`: if the Gateway never saw the transaction it is re-submitted,
+///   otherwise polling resumes until it commits or fails. Once its epoch
+///   window has passed without a final status, the attempt is failed;
+/// - no attempt at all: the proposal is failed so it can be retried.
+///
+/// Attempts the worker is already driving are left alone.
+pub async fn recover_stuck_submissions(submitter: &Submitter) -> Result<()> {
+    let store = &submitter.proposal_store;
+    let stuck = store.list_submitting().await?;
+
+    for proposal in stuck {
+        let latest = store
+            .list_submission_attempts(proposal.id)
+            .await?
+            .into_iter()
+            .last();
+        if latest
+            .as_ref()
+            .is_some_and(|attempt| submitter.queue.is_active(attempt.id))
+        {
+            continue;
+        }
+
+        let ledger = match &latest {
+            Some(attempt) if attempt.status == "submitted" => {
+                match check_on_ledger(&submitter.gateway, attempt).await {
+                    Ok(ledger) => ledger,
+                    Err(e) => {
+                        tracing::warn!("Failed to check attempt {} on ledger: {e:#}", attempt.id);
+                        continue;
+                    }
+                }
+            }
+            _ => None,
+        };
+        let recovery = decide_recovery(
+            latest.as_ref().map(|attempt| attempt.status.as_str()),
+            ledger.as_ref().map(|(status, window_passed)| LedgerCheck {
+                status,
+                window_passed: *window_passed,
+            }),
+        );
+        let tx_hash = latest
+            .as_ref()
+            .and_then(|attempt| attempt.tx_hash.clone())
+            .unwrap_or_default();
+
+        match (recovery, latest) {
+            (Recovery::FailUnpersisted, _) | (_, None) => {
+                tracing::warn!(
+                    "Proposal {} is Submitting without an attempt; failing it",
+                    proposal.id
+                );
+                store
+                    .record_failed_submission(
+                        proposal.id,
+                        None,
+                        FailureReason::ServerError,
+                        "Submission was interrupted before a transaction was persisted",
+                    )
+                    .await?;
+                store
+                    .transition_status(
+                        proposal.id,
+                        ProposalStatus::Submitting,
+                        ProposalStatus::Failed,
+                    )
+                    .await?;
+            }
+            (Recovery::Settle(to), Some(attempt)) => {
+                tracing::info!(
+                    "Recovering proposal {}: attempt {} already {}",
+                    proposal.id,
+                    attempt.id,
+                    attempt.status
+                );
+                store
+                    .transition_status(proposal.id, ProposalStatus::Submitting, to)
+                    .await?;
+            }
+            (Recovery::Submit, Some(attempt)) => {
+                tracing::info!(
+                    "Recovering proposal {}: submitting persisted attempt {}",
+                    proposal.id,
+                    attempt.id
+                );
+                submitter.queue.enqueue(attempt.id)?;
+            }
+            (Recovery::Resubmit, Some(attempt)) => {
+                tracing::info!(
+                    "Recovering proposal {}: Gateway never saw {tx_hash}, re-submitting",
+                    proposal.id
+                );
+                store
+                    .update_submission_attempt(attempt.id, "pending")
+                    .await?;
+                submitter.queue.enqueue(attempt.id)?;
+            }
+            (Recovery::Poll, Some(attempt)) => {
+                tracing::info!(
+                    "Recovering proposal {}: resuming polling of {tx_hash}",
+                    proposal.id
+                );
+                submitter.queue.enqueue(attempt.id)?;
+            }
+            (Recovery::Expire, Some(attempt)) => {
+                tracing::info!(
+                    "Recovering proposal {}: epoch window of {tx_hash} passed without a commit",
+                    proposal.id
+                );
+                fail_attempt(
+                    store,
+                    &attempt,
+                    FailureReason::EpochExpired,
+                    "Transaction's epoch window passed without it committing",
+                )
+                .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The Gateway's status of a submitted attempt's transaction, and whether
+/// its epoch window has passed. `None` if the attempt has no transaction.
+async fn check_on_ledger(
+    gateway: &GatewayClient,
+    attempt: &SubmissionAttempt,
+) -> Result<Option<(TransactionStatusResponse, bool)>> {
+    let Some(tx_hash) = attempt.tx_hash.as_deref() else {
+        return Ok(None);
+    };
+    // Read the epoch before the status, so a commit just before the window
+    // closed is never mistaken for an expiry
+    let window_passed = match attempt.end_epoch_exclusive {
+        Some(end_epoch) => {
+            gateway
+                .get_current_epoch()
+                .await
+                .context("Failed to get current epoch")?
+                >= end_epoch as u64
+        }
+        None => false,
+    };
+    let status = gateway
+        .get_transaction_status(tx_hash)
+        .await
+        .with_context(|| format!("Failed to check status of {tx_hash}"))?;
+    Ok(Some((status, window_passed)))
+}
+
+/// Run [`recover_stuck_submissions`] every `interval_secs`.
+pub async fn run_recovery(submitter: Arc<Submitter>, interval_secs: u64) {
+    let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+    // Startup recovery already ran in [`start`]
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = recover_stuck_submissions(&submitter).await {
+            tracing::error!("Submission recovery error: {e}");
+        }
+    }
+}
+
+/// Submit a Ready auto-submit proposal, recording a failed attempt if the
+/// transaction could not be composed. The proposal then stays Ready so it can
+/// still be submitted manually.
+async fn auto_submit(submitter: &Submitter, proposal_id: Uuid) {
+    match submitter.proposal_store.get(proposal_id).await {
+        Ok(Some(p)) if p.status == ProposalStatus::Ready && p.auto_submit => {}
+        Ok(_) => return,
+        Err(e) => {
+            tracing::error!("Auto-submit failed to load proposal {proposal_id}: {e}");
+            return;
+        }
+    }
+
+    match submitter.submit(proposal_id).await {
+        Ok(started) => tracing::info!(
+            "Auto-submitting proposal {proposal_id} as {} (attempt {})",
+            started.tx_hash,
+            started.attempt_id
+        ),
+        Err(e) => {
+            let err_msg = format!("{e:#}");
+            tracing::error!("Auto-submit failed for proposal {proposal_id}: {err_msg}");
+            if let Err(e) = submitter
+                .proposal_store
+                .record_failed_submission(
+                    proposal_id,
+                    None,
+                    FailureReason::classify(&err_msg, FailureReason::ServerError),
+                    &err_msg,
+                )
+                .await
+            {
+                tracing::error!("Failed to record auto-submit failure for {proposal_id}: {e}");
+            }
+        }
+    }
+}
+
+/// Drive one attempt towards completion: submit it if it has not been sent
+/// yet, then poll the Gateway until it commits or fails.
+///
+/// A transaction can commit until its epoch window has passed, so one still
+/// pending after [`MAX_COMMIT_POLLS`], or whose status could not be read, is
+/// left submitted for [`recover_stuck_submissions`] to pick up again.
+async fn process_attempt(
+    proposal_store: &ProposalStore,
+    gateway: &GatewayClient,
+    attempt_id: Uuid,
+) -> Result<()> {
+    let attempt = proposal_store
+        .get_submission_attempt(attempt_id)
+        .await?
+        .ok_or_else(|| anyhow!("Submission attempt {attempt_id} not found"))?;
+    let tx_hash = attempt
+        .tx_hash
+        .clone()
+        .ok_or_else(|| anyhow!("Submission attempt {attempt_id} has no transaction hash"))?;
+
+    match attempt.status.as_str() {
+        "pending" => {
+            let notarized_transaction_hex = attempt
+                .notarized_transaction_hex
+                .as_deref()
+                .ok_or_else(|| {
+                    anyhow!("Submission attempt {attempt_id} has no notarized transaction")
+                })?;
+
+            // Resubmitting after a restart is safe: the Gateway reports a duplicate.
+            match gateway.submit_transaction(notarized_transaction_hex).await {
+                Ok(duplicate) => {
+                    if duplicate {
+                        tracing::warn!("Transaction {tx_hash} was a duplicate submission");
+                    }
+                    tracing::info!("Transaction submitted: {tx_hash}");
+                    proposal_store
+                        .update_submission_attempt(attempt_id, "submitted")
+                        .await?;
+                    proposal_store
+                        .update_tx_id(attempt.proposal_id, &tx_hash)
+                        .await?;
+                }
+                Err(e) => {
+                    tracing::error!("Submit failed for {tx_hash}: {e}");
+                    let message = e.to_string();
+                    let reason = FailureReason::classify(&message, FailureReason::GatewayError);
+                    return fail_attempt(proposal_store, &attempt, reason, &message).await;
+                }
+            }
+        }
+        "submitted" => {}
+        other => {
+            tracing::debug!("Submission attempt {attempt_id} already finished ({other})");
+            return Ok(());
+        }
+    }
+
+    let outcome = gateway.wait_for_commit(&tx_hash, MAX_COMMIT_POLLS).await;
+    if let Ok(
+        CommitOutcome::Committed {
+            fee_paid: Some(fee_paid),
+        }
+        | CommitOutcome::Failed {
+            fee_paid: Some(fee_paid),
+            ..
+        },
+    ) = &outcome
+    {
+        proposal_store
+            .record_fee_paid(attempt_id, &fee_paid.to_string())
+            .await?;
+    }
+
+    match outcome {
+        Ok(CommitOutcome::Committed { .. }) => {
+            tracing::info!("Transaction committed: {tx_hash}");
+            // The proposal may have changed its account's access rule
+            if let Some(proposal) = proposal_store.get(attempt.proposal_id).await? {
+                gateway.invalidate_access_rule(&proposal.multisig_account);
+            }
+            proposal_store
+                .update_submission_attempt(attempt_id, "committed")
+                .await?;
+            proposal_store
+                .transition_status(
+                    attempt.proposal_id,
+                    ProposalStatus::Submitting,
+                    ProposalStatus::Committed,
+                )
+                .await
+        }
+        Ok(CommitOutcome::Failed {
+            reason, message, ..
+        }) => {
+            tracing::error!("Transaction {tx_hash} did not commit ({reason:?}): {message}");
+            fail_attempt(proposal_store, &attempt, reason, &message).await
+        }
+        Ok(CommitOutcome::Pending) => {
+            tracing::warn!("Transaction {tx_hash} is still pending; recovery will keep polling");
+            Ok(())
+        }
+        Err(e) => {
+            tracing::warn!("Failed to poll status of {tx_hash}; recovery will retry: {e}");
+            Ok(())
+        }
+    }
+}
+
+async fn fail_attempt(
+    proposal_store: &ProposalStore,
+    attempt: &SubmissionAttempt,
+    reason: FailureReason,
+    error_message: &str,
+) -> Result<()> {
+    // Already closed by the validity monitor: another transaction committed the subintent
+    if let Some(current) = proposal_store.get_submission_attempt(attempt.id).await? {
+        if current.status == "failed" {
+            return Ok(());
+        }
+    }
+
+    proposal_store
+        .fail_submission_attempt(attempt.id, reason, error_message)
+        .await?;
+    proposal_store
+        .transition_status(
+            attempt.proposal_id,
+            ProposalStatus::Submitting,
+            ProposalStatus::Failed,
+        )
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> FeeLockPolicy {
+        FeeLockPolicy {
+            margin: "0.2".parse().unwrap(),
+            max: Decimal::from(50u32),
+        }
+    }
+
+    #[test]
+    fn fee_lock_adds_margin_to_estimate() {
+        let lock = policy().lock_amount(Decimal::from(10u32)).unwrap();
+        assert_eq!(lock, Decimal::from(12u32));
+    }
+
+    #[test]
+    fn fee_lock_is_capped() {
+        let lock = policy().lock_amount(Decimal::from(45u32)).unwrap();
+        assert_eq!(lock, Decimal::from(50u32));
+    }
+
+    #[test]
+    fn fee_lock_rejects_estimate_above_cap() {
+        assert!(policy().lock_amount(Decimal::from(51u32)).is_err());
+    }
+
+    #[test]
+    fn enqueue_skips_attempts_already_active() {
+        let (queue, SubmissionJobs(mut jobs)) = queue();
+        let attempt_id = Uuid::new_v4();
+
+        queue.enqueue(attempt_id).unwrap();
+        queue.enqueue(attempt_id).unwrap();
+        assert!(matches!(jobs.try_recv(), Ok(Job::Attempt(id)) if id == attempt_id));
+        assert!(jobs.try_recv().is_err());
+
+        queue.finish(attempt_id);
+        queue.enqueue(attempt_id).unwrap();
+        assert!(matches!(jobs.try_recv(), Ok(Job::Attempt(id)) if id == attempt_id));
+    }
+
+    fn gateway_status(status: &str, known_payloads: usize) -> TransactionStatusResponse {
+        serde_json::from_value(serde_json::json!({
+            "status": status,
+            "known_payloads": vec![serde_json::json!({ "payload_status": status }); known_payloads],
+        }))
+        .unwrap()
+    }
+
+    fn submitted(status: &TransactionStatusResponse, window_passed: bool) -> Recovery {
+        decide_recovery(
+            Some("submitted"),
+            Some(LedgerCheck {
+                status,
+                window_passed,
+            }),
+        )
+    }
+
+    #[test]
+    fn recovery_fails_proposal_without_attempt() {
+        assert_eq!(decide_recovery(None, None), Recovery::FailUnpersisted);
+    }
+
+    #[test]
+    fn recovery_settles_finished_attempts() {
+        assert_eq!(
+            decide_recovery(Some("committed"), None),
+            Recovery::Settle(ProposalStatus::Committed)
+        );
+        assert_eq!(
+            decide_recovery(Some("failed"), None),
+            Recovery::Settle(ProposalStatus::Failed)
+        );
+        assert_eq!(decide_recovery(Some("pending"), None), Recovery::Submit);
+    }
+
+    #[test]
+    fn recovery_resubmits_transaction_unknown_to_gateway() {
+        assert_eq!(
+            submitted(&gateway_status("Unknown", 0), false),
+            Recovery::Resubmit
+        );
+        // Seen by the Gateway, even if not yet pending: keep polling
+        assert_eq!(
+            submitted(&gateway_status("Unknown", 1), false),
+            Recovery::Poll
+        );
+    }
+
+    #[test]
+    fn recovery_keeps_polling_pending_transaction_until_window_passes() {
+        let pending = gateway_status("Pending", 1);
+        assert_eq!(submitted(&pending, false), Recovery::Poll);
+        assert_eq!(submitted(&pending, true), Recovery::Expire);
+        // Never resubmitted once it can no longer commit
+        assert_eq!(
+            submitted(&gateway_status("Unknown", 0), true),
+            Recovery::Expire
+        );
+    }
+
+    #[test]
+    fn recovery_polls_final_status_even_after_window() {
+        for status in ["CommittedSuccess", "CommittedFailure", "Rejected"] {
+            assert_eq!(submitted(&gateway_status(status, 1), true), Recovery::Poll);
+        }
+        assert_eq!(decide_recovery(Some("submitted"), None), Recovery::Poll);
+    }
+
+    #[test]
+    fn ready_proposal_is_auto_submitted_only_when_opted_in() {
+        let (queue, mut jobs) = queue();
+        let opted_in = Uuid::new_v4();
+        let opted_out = Uuid::new_v4();
+
+        queue.proposal_ready(opted_out, false).unwrap();
+        assert_eq!(jobs.try_recv_auto_submit(), None);
+
+        queue.proposal_ready(opted_in, true).unwrap();
+        assert_eq!(jobs.try_recv_auto_submit(), Some(opted_in));
+        assert_eq!(jobs.try_recv_auto_submit(), None);
+    }
+}
@@ -3,8 +3,11 @@ use radix_common::prelude::*;
 use radix_transactions::manifest::compiler::compile_manifest;
 use radix_transactions::manifest::BlobProvider;
 use radix_transactions::prelude::*;
+use radix_transactions::validation::verify_and_recover;
 use rand::Rng;
 
+use crate::notary_signer::NotarySigner;
+
 pub struct SubintentResult {
     pub subintent_hash: String,
     pub intent_discriminator: u64,
@@ -13,7 +16,7 @@ pub struct SubintentResult {
     pub partial_transaction_bytes: Vec<u8>,
 }
 
-fn network_definition(network_id: u8) -> Result<NetworkDefinition> {
+pub fn network_definition(network_id: u8) -> Result<NetworkDefinition> {
     match network_id {
         0xf2 => Ok(NetworkDefinition::simulator()),
         0x02 => Ok(NetworkDefinition::stokenet()),
@@ -41,6 +44,60 @@ pub fn compile_subintent_manifest(
         .map_err(|e| anyhow::anyhow!("Failed to compile manifest: {e:?}"))
 }
 
+/// Prefix subintent manifest text with a `lock_fee` on `account`, so the
+/// subintent pays the transaction fee itself.
+pub fn prepend_lock_fee(manifest_text: &str, account: &str, amount: Decimal) -> String {
+    format!(
+        "CALL_METHOD\n    Address(\"{account}\")\n    \"lock_fee\"\n    Decimal(\"{amount}\")\n;\n{manifest_text}"
+    )
+}
+
+/// Insert instructions paying `amount` XRD from `from_account` back to the
+/// fee payer `to_account`, ahead of the subintent's `YIELD_TO_PARENT` (or at
+/// the end, where [`compile_subintent_manifest`] will append it).
+pub fn append_fee_reimbursement(
+    manifest_text: &str,
+    network_id: u8,
+    from_account: &str,
+    to_account: &str,
+    amount: Decimal,
+) -> Result<String> {
+    let network = network_definition(network_id)?;
+    let xrd = AddressBech32Encoder::new(&network)
+        .encode(XRD.as_bytes())
+        .map_err(|e| anyhow!("Failed to encode XRD address: {e:?}"))?;
+
+    let reimbursement = format!(
+        r#"CALL_METHOD
+    Address("{from_account}")
+    "withdraw"
+    Address("{xrd}")
+    Decimal("{amount}")
+;
+TAKE_FROM_WORKTOP
+    Address("{xrd}")
+    Decimal("{amount}")
+    Bucket("fee_reimbursement")
+;
+CALL_METHOD
+    Address("{to_account}")
+    "try_deposit_or_abort"
+    Bucket("fee_reimbursement")
+    None
+;
+"#
+    );
+
+    Ok(match manifest_text.rfind("YIELD_TO_PARENT") {
+        Some(index) => format!(
+            "{}{reimbursement}{}",
+            &manifest_text[..index],
+            &manifest_text[index..]
+        ),
+        None => format!("{}\n{reimbursement}", manifest_text.trim_end()),
+    })
+}
+
 /// Build an unsigned subintent from raw manifest text.
 ///
 /// Convenience wrapper that compiles the manifest and builds in one step.
@@ -156,6 +213,8 @@ fn build_subintent_from_parts(
 pub struct StoredSignature {
     pub public_key_hex: String,
     pub signature_bytes: Vec<u8>,
+    /// Key type: "EddsaEd25519" or "EcdsaSecp256k1".
+    pub key_type: String,
 }
 
 /// Result of composing a main transaction.
@@ -164,13 +223,15 @@ pub struct ComposedTransaction {
     pub notarized_transaction_hex: String,
     /// Bech32-encoded transaction intent hash (e.g. "txid_tdx_2_1...").
     pub intent_hash: String,
+    /// First epoch in which the transaction can no longer commit.
+    pub end_epoch_exclusive: u64,
 }
 
 /// Reconstruct a signed partial transaction from stored unsigned bytes + collected signatures.
 ///
 /// Takes the original unsigned PartialTransactionV2 (serialized at proposal creation)
-/// and attaches all collected Ed25519 signatures to produce a properly-signed
-/// SignedPartialTransactionV2 for use as a child in the main transaction.
+/// and attaches all collected Ed25519 and Secp256k1 signatures to produce a
+/// properly-signed SignedPartialTransactionV2 for use as a child in the main transaction.
 pub fn reconstruct_signed_partial(
     partial_transaction_bytes: &[u8],
     signatures: &[StoredSignature],
@@ -183,33 +244,10 @@ pub fn reconstruct_signed_partial(
     // Build the signature list from stored data
     let intent_signatures: Vec<IntentSignatureV1> = signatures
         .iter()
-        .map(|s| {
-            let pk_bytes = hex::decode(&s.public_key_hex)
-                .map_err(|e| anyhow!("Invalid public key hex: {e}"))?;
-            if pk_bytes.len() != Ed25519PublicKey::LENGTH {
-                return Err(anyhow!(
-                    "Invalid Ed25519 key length: {} (expected {})",
-                    pk_bytes.len(),
-                    Ed25519PublicKey::LENGTH
-                ));
-            }
-            let mut pk_arr = [0u8; Ed25519PublicKey::LENGTH];
-            pk_arr.copy_from_slice(&pk_bytes);
-
-            if s.signature_bytes.len() != Ed25519Signature::LENGTH {
-                return Err(anyhow!(
-                    "Invalid Ed25519 signature length: {} (expected {})",
-                    s.signature_bytes.len(),
-                    Ed25519Signature::LENGTH
-                ));
-            }
-            let mut sig_arr = [0u8; Ed25519Signature::LENGTH];
-            sig_arr.copy_from_slice(&s.signature_bytes);
-
-            Ok(IntentSignatureV1(SignatureWithPublicKeyV1::Ed25519 {
-                public_key: Ed25519PublicKey(pk_arr),
-                signature: Ed25519Signature(sig_arr),
-            }))
+        .map(|s| match s.key_type.as_str() {
+            "EddsaEd25519" => reconstruct_ed25519_signature(s),
+            "EcdsaSecp256k1" => reconstruct_secp256k1_signature(s),
+            other => Err(anyhow!("Unsupported signature key type: {other}")),
         })
         .collect::<Result<Vec<_>>>()?;
 
@@ -223,88 +261,301 @@ pub fn reconstruct_signed_partial(
     })
 }
 
+/// Hex-encode a signed partial transaction, e.g. for composition into a
+/// transaction the server does not submit.
+pub fn signed_partial_hex(signed_partial: &SignedPartialTransactionV2) -> Result<String> {
+    let raw = signed_partial
+        .to_raw()
+        .map_err(|e| anyhow!("Failed to encode signed partial transaction: {e:?}"))?;
+    Ok(hex::encode(raw.as_slice()))
+}
+
+fn reconstruct_ed25519_signature(s: &StoredSignature) -> Result<IntentSignatureV1> {
+    let pk_bytes =
+        hex::decode(&s.public_key_hex).map_err(|e| anyhow!("Invalid public key hex: {e}"))?;
+    if pk_bytes.len() != Ed25519PublicKey::LENGTH {
+        return Err(anyhow!(
+            "Invalid Ed25519 key length: {} (expected {})",
+            pk_bytes.len(),
+            Ed25519PublicKey::LENGTH
+        ));
+    }
+    let mut pk_arr = [0u8; Ed25519PublicKey::LENGTH];
+    pk_arr.copy_from_slice(&pk_bytes);
+
+    if s.signature_bytes.len() != Ed25519Signature::LENGTH {
+        return Err(anyhow!(
+            "Invalid Ed25519 signature length: {} (expected {})",
+            s.signature_bytes.len(),
+            Ed25519Signature::LENGTH
+        ));
+    }
+    let mut sig_arr = [0u8; Ed25519Signature::LENGTH];
+    sig_arr.copy_from_slice(&s.signature_bytes);
+
+    Ok(IntentSignatureV1(SignatureWithPublicKeyV1::Ed25519 {
+        public_key: Ed25519PublicKey(pk_arr),
+        signature: Ed25519Signature(sig_arr),
+    }))
+}
+
+/// Secp256k1 signatures are recoverable, so only the signature itself is
+/// attached — the engine recovers the public key during validation.
+fn reconstruct_secp256k1_signature(s: &StoredSignature) -> Result<IntentSignatureV1> {
+    if s.signature_bytes.len() != Secp256k1Signature::LENGTH {
+        return Err(anyhow!(
+            "Invalid Secp256k1 signature length: {} (expected {})",
+            s.signature_bytes.len(),
+            Secp256k1Signature::LENGTH
+        ));
+    }
+    let mut sig_arr = [0u8; Secp256k1Signature::LENGTH];
+    sig_arr.copy_from_slice(&s.signature_bytes);
+
+    Ok(IntentSignatureV1(SignatureWithPublicKeyV1::Secp256k1 {
+        signature: Secp256k1Signature(sig_arr),
+    }))
+}
+
+/// Verify every root subintent signature on a signed partial against its subintent hash.
+///
+/// Run before composing so a corrupted or forged stored signature is caught
+/// locally instead of failing at the Gateway after fees are spent.
+pub fn verify_root_signatures(signed_partial: &SignedPartialTransactionV2) -> Result<()> {
+    let prepared = signed_partial
+        .prepare(PreparationSettings::latest_ref())
+        .map_err(|e| anyhow!("Failed to prepare signed partial transaction: {e:?}"))?;
+    let subintent_hash = prepared.subintent_hash();
+
+    for (index, sig) in signed_partial
+        .root_subintent_signatures
+        .signatures
+        .iter()
+        .enumerate()
+    {
+        if verify_and_recover(subintent_hash.as_hash(), &sig.0).is_none() {
+            return Err(anyhow!(
+                "Invalid signature: stored signature #{index} does not verify against the subintent hash"
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// The server keys behind a main transaction.
+#[derive(Clone, Copy)]
+pub struct MainTransactionSigners<'a> {
+    /// Notarizes the transaction.
+    pub notary: &'a dyn NotarySigner,
+    /// Signs the transaction intent to authorise `lock_fee` on the fee payer
+    /// account. `None` makes the notary the signatory (`notary_is_signatory`),
+    /// so the notary key must own the fee payer account.
+    pub fee_payer: Option<&'a dyn NotarySigner>,
+}
+
+/// The `lock_fee` a main intent makes before yielding to its child.
+#[derive(Debug, Clone, Copy)]
+pub struct FeeLock {
+    pub account: ComponentAddress,
+    pub amount: Decimal,
+}
+
+impl MainTransactionSigners<'_> {
+    /// Without a fee lock the notary needs no authority, so it only signs as
+    /// a signatory when it has to authorise the lock itself.
+    fn notary_is_signatory(&self, fee_lock: Option<FeeLock>) -> bool {
+        self.fee_payer.is_none() && fee_lock.is_some()
+    }
+
+    /// Keys that sign the transaction intent, for previews.
+    fn intent_signer_keys(&self) -> Vec<PublicKey> {
+        self.fee_payer
+            .iter()
+            .map(|signer| signer.public_key())
+            .collect()
+    }
+}
+
 /// Compose a complete NotarizedTransactionV2 with:
 /// - Child "withdrawal": DAO signed subintent (with all collected signatures)
-/// - Main intent: lock_fee(fee_payer_account) + yield_to_child("withdrawal")
-/// - Fee paid by server's own account (notary_is_signatory: true)
-/// - Notarized by the server fee payer key
-pub fn compose_main_transaction(
+/// - Main intent: lock_fee(fee_lock) + yield_to_child("withdrawal"), or only
+///   the yield when the subintent locks its own fee (`fee_lock: None`)
+/// - Fee paid by the server's fee payer account, authorised either by the
+///   notary (`notary_is_signatory: true`) or by a separate fee payer signature
+/// - Notarized by the server's notary [`NotarySigner`]
+pub async fn compose_main_transaction(
     network_id: u8,
     current_epoch: u64,
-    fee_payer_private_key: &Ed25519PrivateKey,
-    fee_payer_account: ComponentAddress,
+    signers: MainTransactionSigners<'_>,
+    fee_lock: Option<FeeLock>,
     withdrawal_signed_partial: SignedPartialTransactionV2,
 ) -> Result<ComposedTransaction> {
-    let mut rng = rand::thread_rng();
-    let discriminator: u64 = rng.gen();
+    // Not held across the await below: the thread-local RNG is not Send
+    let discriminator: u64 = rand::thread_rng().gen();
 
     compose_main_transaction_with_discriminator(
         network_id,
         current_epoch,
-        fee_payer_private_key,
-        fee_payer_account,
+        signers,
+        fee_lock,
         withdrawal_signed_partial,
         discriminator,
     )
+    .await
 }
 
 /// Compose main transaction with a specific discriminator (for testing).
-pub fn compose_main_transaction_with_discriminator(
+pub async fn compose_main_transaction_with_discriminator(
     network_id: u8,
     current_epoch: u64,
-    fee_payer_private_key: &Ed25519PrivateKey,
-    fee_payer_account: ComponentAddress,
+    signers: MainTransactionSigners<'_>,
+    fee_lock: Option<FeeLock>,
     withdrawal_signed_partial: SignedPartialTransactionV2,
     discriminator: u64,
 ) -> Result<ComposedTransaction> {
     let network = network_definition(network_id)?;
 
-    let fee_payer_public_key: PublicKey = fee_payer_private_key.public_key().into();
+    let mut builder = main_transaction_builder(
+        network_id,
+        current_epoch,
+        signers.notary.public_key(),
+        signers.notary_is_signatory(fee_lock),
+        fee_lock,
+        withdrawal_signed_partial,
+        discriminator,
+    );
+    if let Some(fee_payer) = signers.fee_payer {
+        let intent_hash = builder.intent_hash();
+        let signature = fee_payer
+            .sign(intent_hash.as_hash())
+            .await
+            .map_err(|e| anyhow!("Fee payer failed to sign: {e:#}"))?;
+        builder = builder.add_signature(with_public_key(fee_payer.public_key(), signature)?);
+    }
+    let signed_intent_hash = builder
+        .create_prepared_signed_transaction_intent()
+        .signed_transaction_intent_hash();
+    let notary_signature = signers
+        .notary
+        .sign(signed_intent_hash.as_hash())
+        .await
+        .map_err(|e| anyhow!("Notary failed to sign: {e:#}"))?;
+    let detailed = builder
+        .notary_signature(notary_signature)
+        .build_no_validate();
 
-    // Build the main transaction with the withdrawal child.
-    // notary_is_signatory: true means the notary's key is also a signatory,
-    // authorising the lock_fee call without a separate .sign() step.
-    let detailed = TransactionV2Builder::new()
+    // Encode the transaction intent hash
+    let encoder = TransactionHashBech32Encoder::new(&network);
+    let intent_hash = encoder
+        .encode(&detailed.transaction_hashes.transaction_intent_hash)
+        .map_err(|e| anyhow!("Failed to encode intent hash: {e:?}"))?;
+
+    // Serialize for submission
+    let notarized_hex = hex::encode(detailed.raw.as_slice());
+
+    Ok(ComposedTransaction {
+        notarized_transaction_hex: notarized_hex,
+        intent_hash,
+        end_epoch_exclusive: current_epoch + MAIN_TRANSACTION_EPOCHS,
+    })
+}
+
+/// Pair a signature with its public key, as intent signatures require.
+fn with_public_key(
+    public_key: PublicKey,
+    signature: SignatureV1,
+) -> Result<SignatureWithPublicKeyV1> {
+    match (public_key, signature) {
+        (PublicKey::Ed25519(public_key), SignatureV1::Ed25519(signature)) => {
+            Ok(SignatureWithPublicKeyV1::Ed25519 {
+                public_key,
+                signature,
+            })
+        }
+        // Recoverable: the engine derives the key from the signature
+        (PublicKey::Secp256k1(_), SignatureV1::Secp256k1(signature)) => {
+            Ok(SignatureWithPublicKeyV1::Secp256k1 { signature })
+        }
+        _ => Err(anyhow!(
+            "Signature type does not match the signer's key type"
+        )),
+    }
+}
+
+/// Epochs, from the current one, in which a main transaction can commit.
+const MAIN_TRANSACTION_EPOCHS: u64 = 100;
+
+/// Start the main transaction that wraps a proposal's subintent:
+/// lock_fee(fee_lock), if any, + yield_to_child("withdrawal").
+fn main_transaction_builder(
+    network_id: u8,
+    current_epoch: u64,
+    notary_public_key: PublicKey,
+    notary_is_signatory: bool,
+    fee_lock: Option<FeeLock>,
+    withdrawal_signed_partial: SignedPartialTransactionV2,
+    discriminator: u64,
+) -> TransactionV2Builder {
+    TransactionV2Builder::new()
         .add_signed_child("withdrawal", withdrawal_signed_partial)
         .transaction_header(TransactionHeaderV2 {
-            notary_public_key: fee_payer_public_key,
-            notary_is_signatory: true,
+            notary_public_key,
+            notary_is_signatory,
             tip_basis_points: 0,
         })
         .intent_header(IntentHeaderV2 {
             network_id,
             start_epoch_inclusive: Epoch::of(current_epoch),
-            end_epoch_exclusive: Epoch::of(current_epoch + 100),
+            end_epoch_exclusive: Epoch::of(current_epoch + MAIN_TRANSACTION_EPOCHS),
             min_proposer_timestamp_inclusive: None,
             max_proposer_timestamp_exclusive: None,
             intent_discriminator: discriminator,
         })
         .manifest_builder(|builder| {
-            builder
-                .lock_fee(fee_payer_account, Decimal::from(10u32))
-                .yield_to_child("withdrawal", ())
+            let builder = match fee_lock {
+                Some(FeeLock { account, amount }) => builder.lock_fee(account, amount),
+                None => builder,
+            };
+            builder.yield_to_child("withdrawal", ())
         })
-        .notarize(fee_payer_private_key)
-        .build_no_validate();
+}
 
-    // Encode the transaction intent hash
-    let encoder = TransactionHashBech32Encoder::new(&network);
-    let intent_hash = encoder
-        .encode(&detailed.transaction_hashes.transaction_intent_hash)
-        .map_err(|e| anyhow!("Failed to encode intent hash: {e:?}"))?;
+/// Build a hex-encoded `PreviewTransactionV2` for `transaction/preview-v2`,
+/// wrapping the proposal subintent exactly as [`compose_main_transaction`]
+/// does. The subintent may be unsigned: the preview is run with
+/// `assume_all_signature_proofs`.
+pub fn build_preview_transaction_hex(
+    network_id: u8,
+    current_epoch: u64,
+    signers: MainTransactionSigners<'_>,
+    fee_lock: Option<FeeLock>,
+    subintent: SignedPartialTransactionV2,
+) -> Result<String> {
+    let mut rng = rand::thread_rng();
+    let preview = main_transaction_builder(
+        network_id,
+        current_epoch,
+        signers.notary.public_key(),
+        signers.notary_is_signatory(fee_lock),
+        fee_lock,
+        subintent,
+        rng.gen(),
+    )
+    // A signatory notary is already a signer, so only a separate fee payer is listed
+    .build_preview_transaction_no_validate(signers.intent_signer_keys());
 
-    // Serialize for submission
-    let notarized_hex = hex::encode(detailed.raw.as_slice());
+    let raw = preview
+        .to_raw()
+        .map_err(|e| anyhow!("Failed to encode preview transaction: {e:?}"))?;
 
-    Ok(ComposedTransaction {
-        notarized_transaction_hex: notarized_hex,
-        intent_hash,
-    })
+    Ok(hex::encode(raw.as_slice()))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::notary_signer::LocalKeySigner;
 
     const TEST_NETWORK_ID: u8 = 2; // Stokenet
 
@@ -481,6 +732,7 @@ CALL_METHOD
                     stored_sigs.push(StoredSignature {
                         public_key_hex: hex::encode(public_key.0),
                         signature_bytes: signature.0.to_vec(),
+                        key_type: "EddsaEd25519".into(),
                     });
                 }
                 _ => panic!("Expected Ed25519"),
@@ -495,8 +747,69 @@ CALL_METHOD
     }
 
     #[test]
-    fn compose_main_transaction_produces_valid_output() {
-        let fee_payer_key = Ed25519PrivateKey::from_u64(10).unwrap();
+    fn reconstruct_signed_partial_with_mixed_key_types() {
+        let subintent = build_unsigned_subintent_with_discriminator(
+            sample_manifest(),
+            TEST_NETWORK_ID,
+            1000,
+            1100,
+            42,
+        )
+        .unwrap();
+
+        let raw =
+            RawSignedPartialTransaction::from_vec(subintent.partial_transaction_bytes.clone());
+        let unsigned = SignedPartialTransactionV2::from_raw(&raw).unwrap();
+        let prepared = unsigned.prepare(PreparationSettings::latest_ref()).unwrap();
+        let subintent_hash = prepared.subintent_hash();
+
+        let ed_key = Ed25519PrivateKey::from_u64(1).unwrap();
+        let secp_key = Secp256k1PrivateKey::from_u64(2).unwrap();
+        let ed_sig = ed_key.sign(subintent_hash);
+        let secp_sig = secp_key.sign(&subintent_hash);
+
+        let stored_sigs = vec![
+            StoredSignature {
+                public_key_hex: hex::encode(ed_key.public_key().0),
+                signature_bytes: ed_sig.0.to_vec(),
+                key_type: "EddsaEd25519".into(),
+            },
+            StoredSignature {
+                public_key_hex: hex::encode(secp_key.public_key().0),
+                signature_bytes: secp_sig.0.to_vec(),
+                key_type: "EcdsaSecp256k1".into(),
+            },
+        ];
+
+        let reconstructed =
+            reconstruct_signed_partial(&subintent.partial_transaction_bytes, &stored_sigs).unwrap();
+
+        verify_root_signatures(&reconstructed).unwrap();
+
+        let sigs = &reconstructed.root_subintent_signatures.signatures;
+        assert_eq!(sigs.len(), 2);
+        assert!(matches!(
+            sigs[0].0,
+            SignatureWithPublicKeyV1::Ed25519 { .. }
+        ));
+        assert_eq!(
+            sigs[1].0,
+            SignatureWithPublicKeyV1::Secp256k1 {
+                signature: secp_sig
+            }
+        );
+    }
+
+    fn notary_only(notary: &dyn NotarySigner) -> MainTransactionSigners<'_> {
+        MainTransactionSigners {
+            notary,
+            fee_payer: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn compose_main_transaction_produces_valid_output() {
+        let fee_payer_key = LocalKeySigner::from_bytes(&[10u8; 32]).unwrap();
         let fee_payer_account =
             ComponentAddress::preallocated_account_from_public_key(&fee_payer_key.public_key());
 
@@ -505,11 +818,15 @@ CALL_METHOD
         let result = compose_main_transaction_with_discriminator(
             TEST_NETWORK_ID,
             1000,
-            &fee_payer_key,
-            fee_payer_account,
+            notary_only(&fee_payer_key),
+            Some(FeeLock {
+                account: fee_payer_account,
+                amount: Decimal::from(10u32),
+            }),
             withdrawal_partial,
             999,
-        );
+        )
+        .await;
 
         assert!(
             result.is_ok(),
@@ -520,11 +837,20 @@ CALL_METHOD
         let composed = result.unwrap();
         assert!(!composed.notarized_transaction_hex.is_empty());
         assert!(composed.intent_hash.starts_with("txid_"));
+        assert_eq!(composed.end_epoch_exclusive, 1100);
+
+        // The notary signature must verify for the transaction to be accepted
+        let raw = RawNotarizedTransaction::from_vec(
+            hex::decode(&composed.notarized_transaction_hex).unwrap(),
+        );
+        let validator =
+            radix_transactions::validation::TransactionValidator::new_with_latest_config_network_agnostic();
+        assert!(raw.validate(&validator).is_ok());
     }
 
-    #[test]
-    fn compose_main_transaction_different_discriminators_produce_different_hashes() {
-        let fee_payer_key = Ed25519PrivateKey::from_u64(10).unwrap();
+    #[tokio::test]
+    async fn compose_main_transaction_different_discriminators_produce_different_hashes() {
+        let fee_payer_key = LocalKeySigner::from_bytes(&[10u8; 32]).unwrap();
         let fee_payer_account =
             ComponentAddress::preallocated_account_from_public_key(&fee_payer_key.public_key());
 
@@ -534,26 +860,205 @@ CALL_METHOD
         let a = compose_main_transaction_with_discriminator(
             TEST_NETWORK_ID,
             1000,
-            &fee_payer_key,
-            fee_payer_account,
+            notary_only(&fee_payer_key),
+            Some(FeeLock {
+                account: fee_payer_account,
+                amount: Decimal::from(10u32),
+            }),
             withdrawal1,
             111,
         )
+        .await
         .unwrap();
 
         let b = compose_main_transaction_with_discriminator(
             TEST_NETWORK_ID,
             1000,
-            &fee_payer_key,
-            fee_payer_account,
+            notary_only(&fee_payer_key),
+            Some(FeeLock {
+                account: fee_payer_account,
+                amount: Decimal::from(10u32),
+            }),
             withdrawal2,
             222,
         )
+        .await
         .unwrap();
 
         assert_ne!(a.intent_hash, b.intent_hash);
     }
 
+    #[tokio::test]
+    async fn compose_main_transaction_with_separate_notary() {
+        let notary_key = LocalKeySigner::from_bytes(&[20u8; 32]).unwrap();
+        let fee_payer_key = LocalKeySigner::from_bytes(&[10u8; 32]).unwrap();
+        let fee_payer_account =
+            ComponentAddress::preallocated_account_from_public_key(&fee_payer_key.public_key());
+
+        let composed = compose_main_transaction_with_discriminator(
+            TEST_NETWORK_ID,
+            1000,
+            MainTransactionSigners {
+                notary: &notary_key,
+                fee_payer: Some(&fee_payer_key),
+            },
+            Some(FeeLock {
+                account: fee_payer_account,
+                amount: Decimal::from(10u32),
+            }),
+            build_test_signed_partial(sample_manifest(), &[1, 2, 3], 200),
+            999,
+        )
+        .await
+        .unwrap();
+
+        let raw = RawNotarizedTransaction::from_vec(
+            hex::decode(&composed.notarized_transaction_hex).unwrap(),
+        );
+        let transaction = NotarizedTransactionV2::from_raw(&raw).unwrap();
+        let signed_intent = &transaction.signed_transaction_intent;
+        let header = &signed_intent.transaction_intent.transaction_header;
+        assert_eq!(header.notary_public_key, notary_key.public_key());
+        assert!(!header.notary_is_signatory);
+
+        // The fee payer authorises lock_fee with its own intent signature
+        let intent_signatures = &signed_intent.transaction_intent_signatures.signatures;
+        assert_eq!(intent_signatures.len(), 1);
+        assert!(matches!(
+            intent_signatures[0].0,
+            SignatureWithPublicKeyV1::Ed25519 { public_key, .. }
+                if PublicKey::from(public_key) == fee_payer_key.public_key()
+        ));
+
+        let validator =
+            radix_transactions::validation::TransactionValidator::new_with_latest_config_network_agnostic();
+        assert!(raw.validate(&validator).is_ok());
+    }
+
+    #[test]
+    fn lock_fee_prepended_to_subintent_manifest() {
+        let account = "account_tdx_2_1cx3u3xgr9anc9fk54dxzsz6k2n6lnadludkx4mx5re5erl8jt9lpnp";
+        let manifest_text = prepend_lock_fee(sample_manifest(), account, Decimal::from(25u32));
+
+        let manifest = compile_subintent_manifest(&manifest_text, TEST_NETWORK_ID).unwrap();
+        assert!(matches!(
+            &manifest.instructions[0],
+            InstructionV2::CallMethod(radix_transactions::manifest::CallMethod { method_name, .. }) if method_name == "lock_fee"
+        ));
+    }
+
+    #[test]
+    fn fee_reimbursement_inserted_before_yield_to_parent() {
+        let from = "account_tdx_2_1cx3u3xgr9anc9fk54dxzsz6k2n6lnadludkx4mx5re5erl8jt9lpnp";
+        let to = "account_tdx_2_12xsvygvltz4uhsht6tdrfxktzpmnl77r0d40j8agmujgdj02el3l9v";
+        let with_yield = format!("{}\nYIELD_TO_PARENT;\n", sample_manifest());
+
+        for manifest_text in [sample_manifest().to_string(), with_yield] {
+            let manifest_text = append_fee_reimbursement(
+                &manifest_text,
+                TEST_NETWORK_ID,
+                from,
+                to,
+                "1.5".parse().unwrap(),
+            )
+            .unwrap();
+            assert!(manifest_text.contains("Decimal(\"1.5\")"));
+
+            let manifest = compile_subintent_manifest(&manifest_text, TEST_NETWORK_ID).unwrap();
+            let instructions = &manifest.instructions;
+            assert!(matches!(
+                instructions.last(),
+                Some(InstructionV2::YieldToParent(_))
+            ));
+            assert!(matches!(
+                &instructions[instructions.len() - 2],
+                InstructionV2::CallMethod(radix_transactions::manifest::CallMethod { method_name, .. })
+                    if method_name == "try_deposit_or_abort"
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn compose_main_transaction_without_fee_lock_only_yields() {
+        let notary_key = LocalKeySigner::from_bytes(&[20u8; 32]).unwrap();
+        let manifest_text = prepend_lock_fee(
+            sample_manifest(),
+            "account_tdx_2_1cx3u3xgr9anc9fk54dxzsz6k2n6lnadludkx4mx5re5erl8jt9lpnp",
+            Decimal::from(25u32),
+        );
+
+        let composed = compose_main_transaction_with_discriminator(
+            TEST_NETWORK_ID,
+            1000,
+            notary_only(&notary_key),
+            None,
+            build_test_signed_partial(&manifest_text, &[1, 2, 3], 200),
+            999,
+        )
+        .await
+        .unwrap();
+
+        let raw = RawNotarizedTransaction::from_vec(
+            hex::decode(&composed.notarized_transaction_hex).unwrap(),
+        );
+        let transaction = NotarizedTransactionV2::from_raw(&raw).unwrap();
+        let intent = &transaction.signed_transaction_intent.transaction_intent;
+        assert!(!intent.transaction_header.notary_is_signatory);
+        assert_eq!(intent.root_intent_core.instructions.0.len(), 1);
+        assert!(matches!(
+            intent.root_intent_core.instructions.0[0],
+            InstructionV2::YieldToChild(_)
+        ));
+    }
+
+    #[test]
+    fn preview_transaction_wraps_unsigned_subintent() {
+        let fee_payer_key = LocalKeySigner::from_bytes(&[10u8; 32]).unwrap();
+        let fee_payer_account =
+            ComponentAddress::preallocated_account_from_public_key(&fee_payer_key.public_key());
+        let subintent = build_unsigned_subintent_with_discriminator(
+            sample_manifest(),
+            TEST_NETWORK_ID,
+            1000,
+            1100,
+            42,
+        )
+        .unwrap();
+
+        let preview_hex = build_preview_transaction_hex(
+            TEST_NETWORK_ID,
+            1000,
+            notary_only(&fee_payer_key),
+            Some(FeeLock {
+                account: fee_payer_account,
+                amount: Decimal::from(10u32),
+            }),
+            reconstruct_signed_partial(&subintent.partial_transaction_bytes, &[]).unwrap(),
+        )
+        .unwrap();
+
+        let raw = RawPreviewTransaction::from_vec(hex::decode(preview_hex).unwrap());
+        let preview = PreviewTransactionV2::from_raw(&raw).unwrap();
+        assert_eq!(preview.transaction_intent.non_root_subintents.0.len(), 1);
+        assert_eq!(preview.non_root_subintent_signer_public_keys, vec![vec![]]);
+        assert!(preview.root_signer_public_keys.is_empty());
+        let validator =
+            radix_transactions::validation::TransactionValidator::new_with_latest_config_network_agnostic();
+        assert!(preview.prepare_and_validate(&validator).is_ok());
+    }
+
+    #[test]
+    fn signed_partial_hex_round_trips() {
+        let signed_partial = build_test_signed_partial(sample_manifest(), &[1, 2], 7);
+
+        let hex = signed_partial_hex(&signed_partial).unwrap();
+
+        let raw = RawSignedPartialTransaction::from_vec(hex::decode(hex).unwrap());
+        let decoded = SignedPartialTransactionV2::from_raw(&raw).unwrap();
+        assert_eq!(decoded, signed_partial);
+        assert!(verify_root_signatures(&decoded).is_ok());
+    }
+
     #[test]
     fn reconstruct_rejects_wrong_key_length() {
         let subintent = build_unsigned_subintent_with_discriminator(
@@ -568,6 +1073,55 @@ CALL_METHOD
         let bad_sig = StoredSignature {
             public_key_hex: "aabbccdd".into(), // Too short
             signature_bytes: vec![0u8; 64],
+            key_type: "EddsaEd25519".into(),
+        };
+
+        let result = reconstruct_signed_partial(&subintent.partial_transaction_bytes, &[bad_sig]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn verify_root_signatures_rejects_corrupted_signature() {
+        let subintent = build_unsigned_subintent_with_discriminator(
+            sample_manifest(),
+            TEST_NETWORK_ID,
+            1000,
+            1100,
+            42,
+        )
+        .unwrap();
+
+        let key = Ed25519PrivateKey::from_u64(1).unwrap();
+        let mut signature_bytes = key.sign(hash("some other message")).0.to_vec();
+        signature_bytes[0] ^= 0xff;
+        let stored_sigs = vec![StoredSignature {
+            public_key_hex: hex::encode(key.public_key().0),
+            signature_bytes,
+            key_type: "EddsaEd25519".into(),
+        }];
+
+        let reconstructed =
+            reconstruct_signed_partial(&subintent.partial_transaction_bytes, &stored_sigs).unwrap();
+
+        let err = verify_root_signatures(&reconstructed).unwrap_err();
+        assert!(err.to_string().starts_with("Invalid signature"), "{err}");
+    }
+
+    #[test]
+    fn reconstruct_rejects_unknown_key_type() {
+        let subintent = build_unsigned_subintent_with_discriminator(
+            sample_manifest(),
+            TEST_NETWORK_ID,
+            1000,
+            1100,
+            42,
+        )
+        .unwrap();
+
+        let bad_sig = StoredSignature {
+            public_key_hex: hex::encode([0u8; 32]),
+            signature_bytes: vec![0u8; 64],
+            key_type: "Unknown".into(),
         };
 
         let result = reconstruct_signed_partial(&subintent.partial_transaction_bytes, &[bad_sig]);
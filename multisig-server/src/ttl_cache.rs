@@ -0,0 +1,135 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// In-process cache for Gateway reads.
+///
+/// Each entry remembers the ledger state version it was read at and expires
+/// after a fixed TTL. A read at an older state version never replaces a
+/// fresher one, so out-of-order responses can't roll the cache back.
+pub struct TtlCache<K, V> {
+    ttl: Duration,
+    entries: Mutex<HashMap<K, Entry<V>>>,
+}
+
+struct Entry<V> {
+    value: V,
+    state_version: u64,
+    fetched_at: Instant,
+}
+
+impl<V> Entry<V> {
+    fn is_fresh(&self, ttl: Duration) -> bool {
+        self.fetched_at.elapsed() < ttl
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> TtlCache<K, V> {
+    /// A zero `ttl` disables caching.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The cached value for `key`, unless it has expired.
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let mut entries = self.lock();
+        match entries.get(key) {
+            Some(entry) if entry.is_fresh(self.ttl) => Some(entry.value.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Cache `value` as read at `state_version`, unless a fresh entry from a
+    /// later state version is already cached.
+    pub fn insert(&self, key: K, value: V, state_version: u64) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let mut entries = self.lock();
+        if let Some(existing) = entries.get(&key) {
+            if existing.is_fresh(self.ttl) && existing.state_version > state_version {
+                return;
+            }
+        }
+        entries.insert(
+            key,
+            Entry {
+                value,
+                state_version,
+                fetched_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Drop the cached value for `key`, so the next read goes to the Gateway.
+    pub fn invalidate<Q>(&self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.lock().remove(key);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<K, Entry<V>>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache() -> TtlCache<String, u32> {
+        TtlCache::new(Duration::from_secs(60))
+    }
+
+    #[test]
+    fn returns_cached_value_until_invalidated() {
+        let cache = cache();
+        cache.insert("account_a".into(), 1, 100);
+        assert_eq!(cache.get("account_a"), Some(1));
+        assert_eq!(cache.get("account_b"), None);
+
+        cache.invalidate("account_a");
+        assert_eq!(cache.get("account_a"), None);
+    }
+
+    #[test]
+    fn older_state_version_does_not_replace_newer() {
+        let cache = cache();
+        cache.insert("account_a".into(), 2, 200);
+        cache.insert("account_a".into(), 1, 100);
+        assert_eq!(cache.get("account_a"), Some(2));
+
+        cache.insert("account_a".into(), 3, 300);
+        assert_eq!(cache.get("account_a"), Some(3));
+    }
+
+    #[test]
+    fn zero_ttl_disables_caching() {
+        let cache = TtlCache::new(Duration::ZERO);
+        cache.insert("account_a".to_string(), 1, 100);
+        assert_eq!(cache.get("account_a"), None);
+    }
+
+    #[test]
+    fn expired_entries_are_not_returned() {
+        let cache = TtlCache::new(Duration::from_millis(10));
+        cache.insert("account_a".to_string(), 1, 100);
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(cache.get("account_a"), None);
+    }
+}
@@ -1,14 +1,21 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 
-use crate::gateway::GatewayClient;
-use crate::proposal_store::{ProposalStatus, ProposalStore};
+use uuid::Uuid;
+
+use crate::gateway::{AccessRuleInfo, GatewayClient};
+use crate::proposal_store::{Proposal, ProposalStatus, ProposalStore, ValidityDecisionKind};
+use crate::submission_worker::SubmissionQueue;
 
 /// Background task that periodically checks active proposals for expiry
 /// and access rule changes, transitioning them to Expired or Invalid as needed.
+/// Readiness is recomputed against the current access rule on every pass.
+/// Proposals whose subintent was committed by any transaction are closed.
 pub async fn run(
     proposal_store: Arc<ProposalStore>,
     gateway: Arc<GatewayClient>,
+    submission_queue: SubmissionQueue,
     interval_secs: u64,
 ) {
     let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
@@ -17,18 +24,33 @@ pub async fn run(
 
     loop {
         interval.tick().await;
-        if let Err(e) = check_proposals(&proposal_store, &gateway).await {
+        if let Err(e) = check_proposals(&proposal_store, &gateway, &submission_queue).await {
             tracing::error!("Validity monitor error: {e}");
         }
     }
 }
 
-/// Check all active proposals for epoch expiry and access rule changes.
+/// Check all active proposals for out-of-band commits, epoch expiry and
+/// access rule changes.
 pub async fn check_proposals(
     proposal_store: &ProposalStore,
     gateway: &GatewayClient,
+    submission_queue: &SubmissionQueue,
 ) -> anyhow::Result<()> {
-    let proposals = proposal_store.list_active().await?;
+    // Phase 0: Close proposals whose subintent was committed by any transaction
+    let mut committed = HashSet::new();
+    for proposal in proposal_store.list_awaiting_commit().await? {
+        if committed_on_ledger(proposal_store, gateway, &proposal).await {
+            committed.insert(proposal.id);
+        }
+    }
+
+    let proposals: Vec<_> = proposal_store
+        .list_active()
+        .await?
+        .into_iter()
+        .filter(|p| !committed.contains(&p.id))
+        .collect();
     if proposals.is_empty() {
         return Ok(());
     }
@@ -53,8 +75,9 @@ pub async fn check_proposals(
         }
     }
 
-    // Phase 2: Check access rule changes for proposals that have signatures.
-    // Group by multisig_account to avoid redundant gateway calls.
+    // Phase 2: Recompute readiness of proposals that have signatures against
+    // the current access rule. Group by multisig_account to avoid redundant
+    // gateway calls.
     let signing_or_ready: Vec<_> = still_active
         .iter()
         .filter(|p| p.status == ProposalStatus::Signing || p.status == ProposalStatus::Ready)
@@ -65,16 +88,17 @@ pub async fn check_proposals(
     }
 
     // Collect unique multisig accounts
-    let unique_accounts: std::collections::HashSet<&str> = signing_or_ready
+    let unique_accounts: HashSet<&str> = signing_or_ready
         .iter()
         .map(|p| p.multisig_account.as_str())
         .collect();
 
-    // Fetch access rules per unique account
-    let mut access_rules: std::collections::HashMap<&str, crate::gateway::AccessRuleInfo> =
+    // Fetch access rules of every account in batched requests
+    let unique_accounts: Vec<&str> = unique_accounts.into_iter().collect();
+    let mut access_rules: std::collections::HashMap<String, AccessRuleInfo> =
         std::collections::HashMap::new();
-    for account in &unique_accounts {
-        match gateway.read_access_rule(account).await {
+    for (account, rule) in gateway.read_access_rules(&unique_accounts).await? {
+        match rule {
             Ok(rule) => {
                 access_rules.insert(account, rule);
             }
@@ -90,65 +114,298 @@ pub async fn check_proposals(
             None => continue, // Already logged above
         };
 
-        let current_hashes: std::collections::HashSet<&str> = access_rule
-            .signers
-            .iter()
-            .map(|s| s.key_hash.as_str())
-            .collect();
-
         // Get signature key hashes for this proposal
-        let sig_hashes = proposal_store
-            .get_signature_key_hashes(proposal.id)
-            .await
-            .unwrap_or_default();
-
-        let mut removed_signers = Vec::new();
-        for (key_hash, is_valid) in &sig_hashes {
-            if *is_valid && !current_hashes.contains(key_hash.as_str()) {
-                // Signer was removed from access rule — invalidate their signature
-                if let Err(e) = proposal_store
-                    .invalidate_signature(proposal.id, key_hash)
-                    .await
-                {
-                    tracing::warn!(
-                        "Failed to invalidate signature for {key_hash} on proposal {}: {e}",
-                        proposal.id
-                    );
-                }
-                removed_signers.push(key_hash.clone());
+        let sig_hashes = match proposal_store.get_signature_key_hashes(proposal.id).await {
+            Ok(sig_hashes) => sig_hashes,
+            Err(e) => {
+                tracing::warn!("Failed to read signatures of proposal {}: {e}", proposal.id);
+                continue;
             }
+        };
+
+        let review = review_signatures(access_rule, &sig_hashes);
+        apply_review(proposal_store, submission_queue, proposal, &review).await;
+    }
+
+    Ok(())
+}
+
+/// How a proposal's signatures stand against the current access rule.
+#[derive(Debug, Default, PartialEq)]
+struct SignatureReview {
+    /// Valid signatures whose signer is no longer in the rule.
+    removed: Vec<String>,
+    /// Invalidated signatures whose signer is back in the rule.
+    readded: Vec<String>,
+    /// Signatures that count once `removed` and `readded` are applied.
+    valid: usize,
+    /// Whether those signatures satisfy the rule.
+    satisfied: bool,
+}
+
+/// What to do with a proposal's status after a [`SignatureReview`].
+#[derive(Debug, PartialEq)]
+enum Readiness {
+    Unchanged,
+    /// Signing→Ready.
+    Ready(String),
+    /// Ready→Signing.
+    BackToSigning(String),
+    /// Mark the proposal Invalid.
+    Invalid(String),
+}
+
+fn review_signatures(
+    access_rule: &AccessRuleInfo,
+    sig_hashes: &[(String, bool)],
+) -> SignatureReview {
+    let current_hashes: HashSet<&str> = access_rule
+        .signers
+        .iter()
+        .map(|s| s.key_hash.as_str())
+        .collect();
+
+    let mut review = SignatureReview::default();
+    let mut valid_hashes = Vec::new();
+    for (key_hash, is_valid) in sig_hashes {
+        let in_rule = current_hashes.contains(key_hash.as_str());
+        match (*is_valid, in_rule) {
+            (true, false) => review.removed.push(key_hash.clone()),
+            (false, true) => review.readded.push(key_hash.clone()),
+            _ => {}
         }
+        if in_rule {
+            valid_hashes.push(key_hash.as_str());
+        }
+    }
+    review.valid = valid_hashes.len();
+    review.satisfied = access_rule.is_satisfied_by(valid_hashes);
+    review
+}
 
-        if !removed_signers.is_empty() {
-            // Recount valid signatures
-            let valid_count = proposal_store
-                .count_valid_signatures(proposal.id)
+/// Recompute a proposal's readiness from a review of its signatures.
+///
+/// Losing the threshold through signer removals invalidates the proposal, as
+/// the signatures it was built on are gone. Losing it because the threshold
+/// went up only sends it back to Signing to collect more.
+fn decide_readiness(status: ProposalStatus, review: &SignatureReview) -> Readiness {
+    if review.satisfied {
+        if status == ProposalStatus::Signing {
+            return Readiness::Ready(format!(
+                "{} valid signature(s) meet the current access rule",
+                review.valid
+            ));
+        }
+        return Readiness::Unchanged;
+    }
+
+    if !review.removed.is_empty() {
+        return Readiness::Invalid(format!(
+            "Access rule changed — signer(s) removed: {}",
+            review
+                .removed
+                .iter()
+                .map(|h| short_hash(h))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    if status == ProposalStatus::Ready {
+        return Readiness::BackToSigning(format!(
+            "{} valid signature(s) no longer meet the current access rule",
+            review.valid
+        ));
+    }
+    Readiness::Unchanged
+}
+
+fn short_hash(hash: &str) -> String {
+    if hash.len() <= 14 {
+        return hash.to_string();
+    }
+    format!("{}...{}", &hash[..8], &hash[hash.len() - 6..])
+}
+
+async fn apply_review(
+    proposal_store: &ProposalStore,
+    submission_queue: &SubmissionQueue,
+    proposal: &Proposal,
+    review: &SignatureReview,
+) {
+    let id = proposal.id;
+    for key_hash in &review.removed {
+        if let Err(e) = proposal_store.invalidate_signature(id, key_hash).await {
+            tracing::warn!("Failed to invalidate signature for {key_hash} on proposal {id}: {e}");
+            continue;
+        }
+        record(
+            proposal_store,
+            id,
+            ValidityDecisionKind::SignatureInvalidated,
+            Some(key_hash),
+            "Signer removed from the access rule",
+        )
+        .await;
+    }
+    for key_hash in &review.readded {
+        if let Err(e) = proposal_store.revalidate_signature(id, key_hash).await {
+            tracing::warn!("Failed to revalidate signature for {key_hash} on proposal {id}: {e}");
+            continue;
+        }
+        record(
+            proposal_store,
+            id,
+            ValidityDecisionKind::SignatureRevalidated,
+            Some(key_hash),
+            "Signer added back to the access rule",
+        )
+        .await;
+    }
+
+    match decide_readiness(proposal.status, review) {
+        Readiness::Unchanged => {}
+        Readiness::Ready(reason) => {
+            tracing::info!("Proposal {id} is ready: {reason}");
+            if let Err(e) = proposal_store
+                .transition_status(id, ProposalStatus::Signing, ProposalStatus::Ready)
+                .await
+            {
+                tracing::warn!("Failed to mark proposal {id} as ready: {e}");
+                return;
+            }
+            record(
+                proposal_store,
+                id,
+                ValidityDecisionKind::Ready,
+                None,
+                &reason,
+            )
+            .await;
+            if let Err(e) = submission_queue.proposal_ready(id, proposal.auto_submit) {
+                tracing::error!("Failed to enqueue auto-submit for {id}: {e}");
+            }
+        }
+        Readiness::BackToSigning(reason) => {
+            tracing::info!("Proposal {id} is back to signing: {reason}");
+            if let Err(e) = proposal_store
+                .transition_status(id, ProposalStatus::Ready, ProposalStatus::Signing)
                 .await
-                .unwrap_or(0);
-
-            if valid_count < access_rule.threshold as i64 {
-                let reason = format!(
-                    "Access rule changed — signer(s) removed: {}",
-                    removed_signers
-                        .iter()
-                        .map(|h| format!("{}...{}", &h[..8], &h[h.len() - 6..]))
-                        .collect::<Vec<_>>()
-                        .join(", ")
+            {
+                tracing::warn!("Failed to move proposal {id} back to signing: {e}");
+                return;
+            }
+            record(
+                proposal_store,
+                id,
+                ValidityDecisionKind::BackToSigning,
+                None,
+                &reason,
+            )
+            .await;
+        }
+        Readiness::Invalid(reason) => {
+            tracing::info!("Proposal {id} invalidated: {reason}");
+            if let Err(e) = proposal_store.mark_invalid(id, &reason).await {
+                tracing::warn!("Failed to mark proposal {id} as invalid: {e}");
+                return;
+            }
+            record(
+                proposal_store,
+                id,
+                ValidityDecisionKind::Invalidated,
+                None,
+                &reason,
+            )
+            .await;
+        }
+    }
+}
+
+async fn record(
+    proposal_store: &ProposalStore,
+    proposal_id: Uuid,
+    decision: ValidityDecisionKind,
+    signer_key_hash: Option<&str>,
+    reason: &str,
+) {
+    if let Err(e) = proposal_store
+        .record_validity_decision(proposal_id, decision, signer_key_hash, reason)
+        .await
+    {
+        tracing::warn!("Failed to record {decision:?} for proposal {proposal_id}: {e}");
+    }
+}
+
+/// Check whether a proposal's subintent has been committed on ledger, and if
+/// so mark the proposal Committed with the transaction that committed it.
+///
+/// Commits by the server's own submission attempts are left to the
+/// submission worker while it is still polling for them. A Failed proposal
+/// is closed whichever transaction committed it.
+async fn committed_on_ledger(
+    proposal_store: &ProposalStore,
+    gateway: &GatewayClient,
+    proposal: &Proposal,
+) -> bool {
+    let Some(subintent_hash) = &proposal.subintent_hash else {
+        return false;
+    };
+    let status = match gateway.get_subintent_status(subintent_hash).await {
+        Ok(status) => status,
+        Err(e) => {
+            tracing::warn!(
+                "Failed to check subintent status of proposal {}: {e}",
+                proposal.id
+            );
+            return false;
+        }
+    };
+    if !status.is_committed() {
+        return false;
+    }
+
+    let tx_id = status.finalized_at_transaction_intent_hash.as_deref();
+    if proposal.status == ProposalStatus::Submitting {
+        let own_attempts = match proposal_store.list_submission_attempts(proposal.id).await {
+            Ok(attempts) => attempts,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to list submission attempts of proposal {}: {e}",
+                    proposal.id
                 );
-                tracing::info!("Proposal {} invalidated: {reason}", proposal.id);
-                if let Err(e) = proposal_store.mark_invalid(proposal.id, &reason).await {
-                    tracing::warn!("Failed to mark proposal {} as invalid: {e}", proposal.id);
-                }
+                return false;
             }
+        };
+        if own_attempts
+            .iter()
+            .any(|attempt| attempt.tx_hash.is_some() && attempt.tx_hash.as_deref() == tx_id)
+        {
+            return false;
         }
     }
 
-    Ok(())
+    // The committing transaction may have changed the account's access rule
+    gateway.invalidate_access_rule(&proposal.multisig_account);
+
+    tracing::info!(
+        "Proposal {} ({:?}) was committed on ledger in {}",
+        proposal.id,
+        proposal.status,
+        tx_id.unwrap_or("an unknown transaction")
+    );
+    if let Err(e) = proposal_store
+        .mark_committed_on_ledger(proposal.id, tx_id)
+        .await
+    {
+        tracing::warn!("Failed to mark proposal {} as committed: {e}", proposal.id);
+        return false;
+    }
+    true
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::gateway::AccessRuleInfo;
+    use super::*;
     use crate::gateway::SignerInfo;
 
     fn make_signer(key_hash: &str) -> SignerInfo {
@@ -169,23 +426,22 @@ mod tests {
 
         // Not expired yet
         let current_epoch: u64 = 1099;
-        assert!(!(current_epoch >= epoch_max as u64));
+        assert!(current_epoch < epoch_max as u64);
     }
 
     #[test]
     fn access_rule_signer_removal_detection() {
-        let original_signers = vec![
+        let original_signers = [
             make_signer("aaaa1111bbbb2222cccc3333dddd4444eeee5555ffff6666aabb"),
             make_signer("1111aaaa2222bbbb3333cccc4444dddd5555eeee6666ffffaabb"),
             make_signer("5555666677778888aaaa1111bbbb2222cccc3333dddd4444eeff"),
         ];
 
         // After access rule change, one signer was removed
-        let new_access_rule = AccessRuleInfo {
-            signers: vec![original_signers[0].clone(), original_signers[2].clone()],
-            threshold: 2,
-            is_updatable: true,
-        };
+        let new_access_rule = AccessRuleInfo::count_of(
+            vec![original_signers[0].clone(), original_signers[2].clone()],
+            2,
+        );
 
         let current_hashes: std::collections::HashSet<&str> = new_access_rule
             .signers
@@ -209,14 +465,148 @@ mod tests {
 
     #[test]
     fn threshold_check_with_invalidated_sigs() {
-        let threshold: u8 = 3;
+        let signers = vec![make_signer("aa"), make_signer("bb"), make_signer("cc")];
+        let access_rule = AccessRuleInfo::count_of(signers, 3);
 
         // 3 valid signatures, but one gets invalidated
-        let valid_count: i64 = 2;
-        assert!(valid_count < threshold as i64); // Should mark Invalid
+        assert!(!access_rule.is_satisfied_by(["aa", "cc"])); // Should mark Invalid
 
         // Still enough valid signatures
-        let valid_count: i64 = 3;
-        assert!(!(valid_count < threshold as i64)); // Should remain active
+        assert!(access_rule.is_satisfied_by(["aa", "bb", "cc"])); // Should remain active
+    }
+
+    fn sigs(sigs: &[(&str, bool)]) -> Vec<(String, bool)> {
+        sigs.iter()
+            .map(|(hash, valid)| (hash.to_string(), *valid))
+            .collect()
+    }
+
+    #[test]
+    fn threshold_increase_sends_ready_back_to_signing() {
+        let signers = vec![make_signer("aa"), make_signer("bb"), make_signer("cc")];
+        let raised = AccessRuleInfo::count_of(signers, 3);
+
+        let review = review_signatures(&raised, &sigs(&[("aa", true), ("bb", true)]));
+        assert!(review.removed.is_empty());
+        assert!(!review.satisfied);
+        assert!(matches!(
+            decide_readiness(ProposalStatus::Ready, &review),
+            Readiness::BackToSigning(_)
+        ));
+        assert_eq!(
+            decide_readiness(ProposalStatus::Signing, &review),
+            Readiness::Unchanged
+        );
+    }
+
+    #[test]
+    fn readded_signer_is_revalidated_and_restores_readiness() {
+        let signers = vec![make_signer("aa"), make_signer("bb"), make_signer("cc")];
+        let access_rule = AccessRuleInfo::count_of(signers, 2);
+
+        let review = review_signatures(&access_rule, &sigs(&[("aa", true), ("bb", false)]));
+        assert_eq!(review.readded, vec!["bb".to_string()]);
+        assert_eq!(review.valid, 2);
+        assert!(review.satisfied);
+        assert!(matches!(
+            decide_readiness(ProposalStatus::Signing, &review),
+            Readiness::Ready(_)
+        ));
+        assert_eq!(
+            decide_readiness(ProposalStatus::Ready, &review),
+            Readiness::Unchanged
+        );
+    }
+
+    #[test]
+    fn signer_removal_below_threshold_invalidates() {
+        let access_rule = AccessRuleInfo::count_of(vec![make_signer("aa"), make_signer("cc")], 2);
+
+        let review = review_signatures(&access_rule, &sigs(&[("aa", true), ("bb", true)]));
+        assert_eq!(review.removed, vec!["bb".to_string()]);
+        assert!(matches!(
+            decide_readiness(ProposalStatus::Ready, &review),
+            Readiness::Invalid(_)
+        ));
+    }
+
+    #[test]
+    fn signer_removal_above_threshold_keeps_readiness() {
+        let access_rule = AccessRuleInfo::count_of(vec![make_signer("aa"), make_signer("cc")], 1);
+
+        let review = review_signatures(&access_rule, &sigs(&[("aa", true), ("bb", true)]));
+        assert_eq!(review.removed, vec!["bb".to_string()]);
+        assert!(review.satisfied);
+        assert_eq!(
+            decide_readiness(ProposalStatus::Ready, &review),
+            Readiness::Unchanged
+        );
+    }
+
+    async fn signing_proposal(proposal_store: &ProposalStore, auto_submit: bool) -> Proposal {
+        let proposal = proposal_store
+            .create(crate::proposal_store::CreateProposal {
+                manifest_text: "YIELD_TO_PARENT;".into(),
+                multisig_account: "account_tdx_2_test".into(),
+                epoch_min: 1000,
+                epoch_max: 1100,
+                subintent_hash: Uuid::new_v4().to_string(),
+                intent_discriminator: 12345,
+                min_proposer_timestamp: 0,
+                max_proposer_timestamp: 0,
+                partial_transaction_bytes: Vec::new(),
+                proposer_key_hash: None,
+                auto_submit,
+                preview: None,
+                fee_mode: Default::default(),
+                reimbursed_fee_payer: None,
+                fee_reimbursement: None,
+            })
+            .await
+            .unwrap();
+        proposal_store
+            .transition_status(
+                proposal.id,
+                ProposalStatus::Created,
+                ProposalStatus::Signing,
+            )
+            .await
+            .unwrap();
+        proposal_store.get(proposal.id).await.unwrap().unwrap()
+    }
+
+    /// Moves a Signing proposal with and one without `auto_submit` to Ready.
+    /// Needs a Postgres database: `DATABASE_URL=... cargo test -- --ignored`.
+    #[test]
+    #[ignore]
+    fn integration_ready_proposal_is_auto_submitted_only_when_opted_in() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        rt.block_on(async {
+            let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+            let pool = sqlx::PgPool::connect(&database_url).await.unwrap();
+            sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+            let proposal_store = ProposalStore::new(pool);
+
+            let opted_in = signing_proposal(&proposal_store, true).await;
+            let opted_out = signing_proposal(&proposal_store, false).await;
+
+            let (queue, mut jobs) = crate::submission_worker::queue();
+            let review = SignatureReview {
+                valid: 2,
+                satisfied: true,
+                ..Default::default()
+            };
+
+            apply_review(&proposal_store, &queue, &opted_out, &review).await;
+            assert_eq!(jobs.try_recv_auto_submit(), None);
+
+            apply_review(&proposal_store, &queue, &opted_in, &review).await;
+            assert_eq!(jobs.try_recv_auto_submit(), Some(opted_in.id));
+
+            for proposal in [&opted_in, &opted_out] {
+                let proposal = proposal_store.get(proposal.id).await.unwrap().unwrap();
+                assert_eq!(proposal.status, ProposalStatus::Ready);
+            }
+        });
     }
 }